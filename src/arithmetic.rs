@@ -0,0 +1,135 @@
+use rand::Rng;
+use rand::rngs::StdRng;
+
+use crate::word_sources::{ self, WordSource };
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operation {
+    Add,
+    Subtract,
+    Multiply,
+    Divide
+}
+
+impl Operation {
+    pub fn symbol(&self) -> char {
+        match self {
+            Operation::Add => '+',
+            Operation::Subtract => '-',
+            Operation::Multiply => '×',
+            Operation::Divide => '÷'
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tier {
+    // no operations means plain numbers to type
+    pub operations: &'static [Operation],
+    // largest number of a plain number, a sum or a difference
+    pub max_operand: u32,
+    // largest factor of a product, quotients are products the other way around
+    pub max_factor: u32
+}
+
+// easiest first
+pub const TIERS: [Tier; 6] = [
+    Tier { operations: &[], max_operand: 99, max_factor: 0 },
+    Tier { operations: &[], max_operand: 99_999, max_factor: 0 },
+    Tier { operations: &[Operation::Add], max_operand: 10, max_factor: 0 },
+    Tier { operations: &[Operation::Add, Operation::Subtract], max_operand: 50, max_factor: 0 },
+    Tier { operations: &[Operation::Add, Operation::Subtract, Operation::Multiply], max_operand: 100, max_factor: 10 },
+    Tier { operations: &[Operation::Add, Operation::Subtract, Operation::Multiply, Operation::Divide], max_operand: 999, max_factor: 15 }
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub prompt: String,
+    pub answer: String
+}
+
+// answers are never negative, so they are typed with digits only
+pub fn generate<R: Rng>(tier: &Tier, rng: &mut R) -> Problem {
+    if tier.operations.is_empty() {
+        let number = rng.gen_range(0 ..= tier.max_operand).to_string();
+        return Problem { prompt: number.clone(), answer: number };
+    }
+
+    let operation = tier.operations[rng.gen_range(0 .. tier.operations.len())];
+    let (first, second, answer) = match operation {
+        Operation::Add => {
+            let first = rng.gen_range(0 ..= tier.max_operand);
+            let second = rng.gen_range(0 ..= tier.max_operand);
+            (first, second, first + second)
+        },
+        Operation::Subtract => {
+            let first = rng.gen_range(0 ..= tier.max_operand);
+            let second = rng.gen_range(0 ..= first);
+            (first, second, first - second)
+        },
+        Operation::Multiply => {
+            let first = rng.gen_range(0 ..= tier.max_factor);
+            let second = rng.gen_range(0 ..= tier.max_factor);
+            (first, second, first * second)
+        },
+        Operation::Divide => {
+            let divisor = rng.gen_range(1 ..= tier.max_factor);
+            let quotient = rng.gen_range(0 ..= tier.max_factor);
+            (divisor * quotient, divisor, quotient)
+        }
+    };
+
+    Problem {
+        prompt: format!("{} {} {}", first, operation.symbol(), second),
+        answer: answer.to_string()
+    }
+}
+
+pub fn tier_for(level: f32) -> usize {
+    (level.clamp(0.0, 1.0) * (TIERS.len() - 1) as f32).round() as usize
+}
+
+// numbers and problems which get harder with the game speed
+#[derive(Debug)]
+pub struct ArithmeticSource {
+    tier: usize,
+    // the prompt of the last given answer, different problems can have the same answer
+    last: Option<Problem>,
+    rng: StdRng
+}
+
+impl ArithmeticSource {
+    pub fn new(seed: Option<u64>) -> Self {
+        ArithmeticSource {
+            tier: 0,
+            last: None,
+            rng: word_sources::seeded_rng(seed)
+        }
+    }
+
+    pub fn tier(&self) -> usize {
+        self.tier
+    }
+}
+
+impl WordSource for ArithmeticSource {
+    fn name(&self) -> &str {
+        "Arithmetic"
+    }
+
+    fn next_word(&mut self) -> Option<String> {
+        let problem = generate(&TIERS[self.tier], &mut self.rng);
+        let answer = problem.answer.clone();
+        self.last = Some(problem);
+
+        Some(answer)
+    }
+
+    fn set_difficulty(&mut self, level: f32) {
+        self.tier = tier_for(level);
+    }
+
+    fn prompt(&self, word: &str) -> Option<String> {
+        self.last.as_ref().filter(|problem| problem.answer == word).map(|problem| problem.prompt.clone())
+    }
+}
@@ -26,11 +26,16 @@ pub trait Sprite: Debug {
     fn draw(&mut self, top_left: Point2<f32>, color: Color, ctx: &mut Context) -> GameResult<()>;
     fn width(&self, ctx: &mut Context) -> f32;
     fn height(&self, ctx: &mut Context) -> f32;
+
+    // sprites without text have nothing to change
+    fn set_label(&mut self, _label: &str) {}
 }
 
 #[derive(Debug)]
 pub struct TextSprite {
     text: graphics::Text,
+    font: graphics::Font,
+    font_size: f32
 }
 
 impl TextSprite {
@@ -38,7 +43,7 @@ impl TextSprite {
         let font = graphics::Font::new(ctx, "/RedHatDisplay-Regular.otf")?;
         let mut text = graphics::Text::new(label);
         text.set_font(font, graphics::PxScale::from(font_size));
-        Ok(TextSprite { text })
+        Ok(TextSprite { text, font, font_size })
     }
 }
 
@@ -54,4 +59,9 @@ impl Sprite for TextSprite {
     fn height(&self, ctx: &mut Context) -> f32 {
         self.text.height(ctx)
     }
+
+    fn set_label(&mut self, label: &str) {
+        self.text = graphics::Text::new(label);
+        self.text.set_font(self.font, graphics::PxScale::from(self.font_size));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,179 @@
+use crate::modes::GameMode;
+use crate::cli::SourceKind;
+
+// everything needed to play the same run again
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ruleset {
+    pub seed: u64,
+    pub mode: GameMode,
+    // 0 ..= MAX_DIFFICULTY, how fast the game starts
+    pub difficulty: u8,
+    // where the words come from and the settings of the generator
+    pub source: SourceKind,
+    pub order: u8,
+    pub min_length: u8,
+    pub max_length: u8,
+    // the family friendly filter, with the shipped blocklist and tags
+    pub content_filter: bool,
+    pub pack: String,
+    // names of the running modifiers
+    pub modifiers: Vec<String>
+}
+
+pub const MAX_DIFFICULTY: u8 = 10;
+
+// bumped whenever the layout of the code changes, older codes are refused
+pub const VERSION: u8 = 1;
+
+const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+// characters between the dashes of a written code
+const GROUP_SIZE: usize = 4;
+// one bit for each modifier
+const MAX_MODIFIERS: usize = 16;
+// bytes before the pack: version, mode, difficulty, source, order, lengths, filter, seed, modifiers and the pack length
+const HEADER_LENGTH: usize = 19;
+// the header and the checksum
+const MIN_LENGTH: usize = HEADER_LENGTH + 2;
+
+// CRC-16/CCITT-FALSE
+fn checksum(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+
+    for byte in bytes {
+        crc ^= (*byte as u16) << 8;
+
+        for _ in 0 .. 8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+        }
+    }
+
+    crc
+}
+
+fn to_base32(bytes: &[u8]) -> String {
+    let mut text = String::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+
+    for byte in bytes {
+        buffer = (buffer << 8) | *byte as u32;
+        bits += 8;
+
+        while bits >= 5 {
+            bits -= 5;
+            text.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+
+    if bits > 0 {
+        text.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+
+    text
+}
+
+fn from_base32(text: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+
+    for letter in text.chars() {
+        let value = ALPHABET.iter().position(|known| *known as char == letter).ok_or(format!("'{}' can't be in a challenge code", letter))?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+
+    // the bits after the last byte are always zero, otherwise a typo in the last letter would go unnoticed
+    if bits >= 5 || buffer & ((1 << bits) - 1) != 0 {
+        return Err(String::from("the challenge code doesn't end where it should"));
+    }
+
+    Ok(bytes)
+}
+
+// the modifiers are stored as bits of their places in `modifier_names`
+pub fn encode(ruleset: &Ruleset, modifier_names: &[String]) -> String {
+    let mode = GameMode::ALL.iter().position(|mode| *mode == ruleset.mode).unwrap_or(0) as u8;
+    let modifiers = modifier_names.iter().
+        take(MAX_MODIFIERS).
+        enumerate().
+        filter(|(_, name)| ruleset.modifiers.contains(name)).
+        fold(0u16, |mask, (index, _)| mask | (1 << index));
+    let pack = &ruleset.pack.as_bytes()[.. ruleset.pack.len().min(u8::MAX as usize)];
+
+    let source = SourceKind::ALL.iter().position(|source| *source == ruleset.source).unwrap_or(0) as u8;
+
+    let mut bytes = vec![VERSION, mode, ruleset.difficulty, source, ruleset.order, ruleset.min_length, ruleset.max_length, ruleset.content_filter as u8];
+    bytes.extend(ruleset.seed.to_be_bytes());
+    bytes.extend(modifiers.to_be_bytes());
+    bytes.push(pack.len() as u8);
+    bytes.extend(pack);
+    bytes.extend(checksum(&bytes).to_be_bytes());
+
+    let text = to_base32(&bytes);
+    let groups = text.as_bytes().chunks(GROUP_SIZE).map(|group| String::from_utf8_lossy(group).to_string()).collect::<Vec<String>>();
+
+    groups.join("-")
+}
+
+// dashes, spaces and the case of the letters don't matter
+pub fn decode(code: &str, modifier_names: &[String]) -> Result<Ruleset, String> {
+    let text = code.chars().filter(|letter| *letter != '-' && !letter.is_whitespace()).collect::<String>().to_uppercase();
+    let bytes = from_base32(&text)?;
+
+    if bytes.len() < MIN_LENGTH {
+        return Err(String::from("the challenge code is too short"));
+    }
+
+    let (data, sum) = bytes.split_at(bytes.len() - 2);
+    if checksum(data).to_be_bytes() != sum {
+        return Err(String::from("the challenge code has a typo, its checksum doesn't match"));
+    }
+
+    if data[0] != VERSION {
+        return Err(format!("the challenge code is from version {} of the format, this game reads version {}", data[0], VERSION));
+    }
+
+    let mode = *GameMode::ALL.get(data[1] as usize).ok_or(format!("unknown mode {} in the challenge code", data[1]))?;
+    let difficulty = data[2];
+    if difficulty > MAX_DIFFICULTY {
+        return Err(format!("difficulty {} in the challenge code is above {}", difficulty, MAX_DIFFICULTY));
+    }
+
+    let source = *SourceKind::ALL.get(data[3] as usize).ok_or(format!("unknown word source {} in the challenge code", data[3]))?;
+    let (order, min_length, max_length) = (data[4], data[5], data[6]);
+    if min_length > max_length {
+        return Err(String::from("the challenge code has a shortest word longer than its longest one"));
+    }
+
+    let content_filter = match data[7] {
+        0 => false,
+        1 => true,
+        _ => return Err(String::from("the challenge code has a broken filter setting"))
+    };
+
+    let seed = u64::from_be_bytes(data[8 .. 16].try_into().unwrap());
+    let mask = u16::from_be_bytes([data[16], data[17]]);
+    let pack_length = data[18] as usize;
+
+    if (mask as u32) >> modifier_names.len().min(MAX_MODIFIERS) != 0 {
+        return Err(String::from("the challenge code has modifiers this game doesn't know"));
+    }
+
+    if data.len() != HEADER_LENGTH + pack_length {
+        return Err(String::from("the challenge code has the wrong length"));
+    }
+
+    let pack = String::from_utf8(data[HEADER_LENGTH ..].to_vec()).map_err(|_| String::from("the pack in the challenge code isn't text"))?;
+    let modifiers = modifier_names.iter().
+        take(MAX_MODIFIERS).
+        enumerate().
+        filter(|(index, _)| mask & (1 << index) != 0).
+        map(|(_, name)| name.clone()).
+        collect();
+
+    Ok(Ruleset { seed, mode, difficulty, source, order, min_length, max_length, content_filter, pack, modifiers })
+}
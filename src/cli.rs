@@ -0,0 +1,154 @@
+use crate::modes::GameMode;
+use crate::modifiers::ModifierRegistry;
+use crate::challenge::{ self, Ruleset };
+use crate::word_sources::MarkovGenerator;
+
+pub const USAGE: &str =
+"usage: type_racer [options]
+       type_racer validate <dictionary> [--write <normalized dictionary>]
+
+    --source <dictionary|generated|passage>   where the words come from (dictionary)
+    --passage <path>                          text for the passage source (/passage.txt)
+    --order <number>                          letters the generator looks back at (3)
+    --min-length <number>                     shortest generated word (4)
+    --max-length <number>                     longest generated word (9)
+    --seed <number>                           same seed, same words
+    --pack <id>                               start right away with the given word pack
+    --mode <id>                               classic, adaptive, review, vocabulary,
+                                              arithmetic, chain, memory, daily or campaign (classic)
+    --difficulty <0-10>                       how fast the game starts (0)
+    --challenge <code>                        play the run of a challenge code from the game over screen
+    --profile <name>                          who plays the daily challenge, remembered for the next games";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Play(Options),
+    // checks a dictionary file and optionally writes a cleaned up copy of it
+    Validate { path: String, output: Option<String> }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SourceKind {
+    Dictionary,
+    Generated,
+    Passage
+}
+
+impl SourceKind {
+    pub const ALL: [SourceKind; 3] = [SourceKind::Dictionary, SourceKind::Generated, SourceKind::Passage];
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub source: SourceKind,
+    // path inside the resources
+    pub passage: String,
+    pub order: usize,
+    pub min_length: usize,
+    pub max_length: usize,
+    pub seed: Option<u64>,
+    // directory name of the word pack
+    pub pack: Option<String>,
+    pub mode: GameMode,
+    pub difficulty: u8,
+    // replaces the seed, mode, difficulty, source and generator options, filter, pack and modifiers
+    pub challenge: Option<Ruleset>,
+    pub profile: Option<String>
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            source: SourceKind::Dictionary,
+            passage: String::from("/passage.txt"),
+            order: MarkovGenerator::DEFAULT_ORDER,
+            min_length: MarkovGenerator::DEFAULT_MIN_LENGTH,
+            max_length: MarkovGenerator::DEFAULT_MAX_LENGTH,
+            seed: None,
+            pack: None,
+            mode: GameMode::default(),
+            difficulty: 0,
+            challenge: None,
+            profile: None
+        }
+    }
+}
+
+fn usage_error(message: String) -> String {
+    format!("{}\n\n{}", message, USAGE)
+}
+
+fn parse_number<T: std::str::FromStr>(option: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| usage_error(format!("{} expects a number, got '{}'", option, value)))
+}
+
+// the arguments without the program name, the error is ready to be printed
+pub fn parse_command<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.into_iter().peekable();
+
+    if args.peek().map(|command| command.as_str()) != Some("validate") {
+        return parse(args).map(Command::Play);
+    }
+
+    args.next();
+    let path = args.next().ok_or_else(|| usage_error(String::from("validate expects a dictionary")))?;
+
+    let output = match (args.next(), args.next()) {
+        (None, _) => None,
+        (Some(option), Some(output)) if option == "--write" => Some(output),
+        (Some(option), _) => return Err(usage_error(format!("unexpected '{}' after the dictionary", option)))
+    };
+
+    if let Some(extra) = args.next() {
+        return Err(usage_error(format!("unexpected '{}' after the dictionary", extra)));
+    }
+
+    Ok(Command::Validate { path, output })
+}
+
+// the options of a normal game
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+
+    while let Some(option) = args.next() {
+        if option == "--help" {
+            return Err(String::from(USAGE));
+        }
+
+        let value = args.next().ok_or_else(|| usage_error(format!("{} expects a value", option)))?;
+
+        match option.as_str() {
+            "--source" => {
+                options.source = match value.as_str() {
+                    "dictionary" => SourceKind::Dictionary,
+                    "generated" => SourceKind::Generated,
+                    "passage" => SourceKind::Passage,
+                    _ => return Err(usage_error(format!("unknown word source '{}'", value)))
+                }
+            },
+            "--passage" => options.passage = value,
+            "--order" => options.order = parse_number(&option, &value)?,
+            "--min-length" => options.min_length = parse_number(&option, &value)?,
+            "--max-length" => options.max_length = parse_number(&option, &value)?,
+            "--seed" => options.seed = Some(parse_number(&option, &value)?),
+            "--pack" => options.pack = Some(value),
+            "--profile" if value.trim().is_empty() || value.contains(['\t', '\n']) => return Err(usage_error(format!("'{}' can't be a profile name", value))),
+            "--profile" => options.profile = Some(value.trim().to_string()),
+            "--difficulty" => options.difficulty = parse_number(&option, &value)?,
+            "--challenge" => options.challenge = Some(challenge::decode(&value, &ModifierRegistry::with_defaults().names()).map_err(usage_error)?),
+            "--mode" => options.mode = GameMode::from_id(&value).ok_or_else(|| usage_error(format!("unknown mode '{}'", value)))?,
+            _ => return Err(usage_error(format!("unknown option '{}'", option)))
+        }
+    }
+
+    if options.difficulty > challenge::MAX_DIFFICULTY {
+        return Err(usage_error(format!("--difficulty can't be above {}", challenge::MAX_DIFFICULTY)));
+    }
+
+    if options.min_length > options.max_length {
+        return Err(usage_error(String::from("--min-length can't be above --max-length")));
+    }
+
+    Ok(options)
+}
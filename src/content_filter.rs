@@ -0,0 +1,70 @@
+use std::collections::{ HashMap, HashSet };
+
+// lines which aren't comments or blank
+fn content_lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines().map(|line| line.trim()).filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+// tags of single words, read from `word: tag, tag` lines
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WordTags {
+    tags: HashMap<String, Vec<String>>
+}
+
+impl WordTags {
+    // lines without a colon are skipped
+    pub fn parse(text: &str) -> Self {
+        let tags = content_lines(text).
+            filter_map(|line| line.split_once(':')).
+            map(|(word, tags)| {
+                let tags = tags.split(',').map(|tag| tag.trim().to_lowercase()).filter(|tag| !tag.is_empty()).collect();
+                (word.trim().to_lowercase(), tags)
+            }).
+            collect();
+
+        WordTags { tags }
+    }
+
+    pub fn tags(&self, word: &str) -> &[String] {
+        self.tags.get(&word.to_lowercase()).map(|tags| tags.as_slice()).unwrap_or(&[])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filtered {
+    pub words: Vec<String>,
+    // how many words were left out
+    pub removed: usize
+}
+
+// hides blocked words and words with an excluded tag
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ContentFilter {
+    blocked: HashSet<String>,
+    excluded_tags: Vec<String>
+}
+
+impl ContentFilter {
+    // the blocklist has a word on every line, case doesn't matter
+    pub fn new(blocklist: &str, excluded_tags: &[String]) -> Self {
+        ContentFilter {
+            blocked: content_lines(blocklist).map(|word| word.to_lowercase()).collect(),
+            excluded_tags: excluded_tags.iter().map(|tag| tag.to_lowercase()).collect()
+        }
+    }
+
+    pub fn allows(&self, word: &str, word_tags: &WordTags) -> bool {
+        !self.blocked.contains(&word.to_lowercase()) &&
+        !word_tags.tags(word).iter().any(|tag| self.excluded_tags.contains(tag))
+    }
+
+    pub fn apply(&self, words: Vec<String>, word_tags: &WordTags) -> Filtered {
+        let total = words.len();
+        let words = words.into_iter().filter(|word| self.allows(word, word_tags)).collect::<Vec<String>>();
+
+        Filtered {
+            removed: total - words.len(),
+            words
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use rand::{ Rng, SeedableRng, seq };
+use rand::rngs::StdRng;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::{ SystemTime, UNIX_EPOCH };
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+// a day of the calendar, in UTC so everyone gets the same challenge at the same time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32
+}
+
+impl Date {
+    pub fn today() -> Self {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0);
+        Date::from_days((seconds / SECONDS_PER_DAY) as i64)
+    }
+
+    // days since 1970-01-01
+    pub fn from_days(days: i64) -> Self {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_from_march = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+        let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Date { year: year as i32, month: month as u32, day: day as u32 }
+    }
+
+    pub fn days(&self) -> i64 {
+        let year = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = self.month as i64;
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    // 0 is Monday
+    pub fn weekday(&self) -> u32 {
+        // 1970-01-01 was a Thursday
+        (self.days() + 3).rem_euclid(7) as u32
+    }
+
+    pub fn days_in_month(year: i32, month: u32) -> u32 {
+        let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+        (Date { year: next_year, month: next_month, day: 1 }.days() - Date { year, month, day: 1 }.days()) as u32
+    }
+
+    // `YYYY-MM-DD`
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.trim().splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+
+        if !(1 ..= 12).contains(&month) || day < 1 || day > Date::days_in_month(year, month) {
+            return None;
+        }
+
+        Some(Date { year, month, day })
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+pub const MONTH_NAMES: [&str; 12] = ["January", "February", "March", "April", "May", "June",
+                                     "July", "August", "September", "October", "November", "December"];
+pub const WEEKDAY_NAMES: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+// the days of the month by weeks starting on Monday, days of the other months are `None`
+pub fn month_weeks(year: i32, month: u32) -> Vec<[Option<u32>; 7]> {
+    let first = Date { year, month, day: 1 }.weekday() as usize;
+    let mut weeks = vec![[None; 7]];
+
+    for day in 1 ..= Date::days_in_month(year, month) {
+        let cell = first + day as usize - 1;
+
+        if cell / 7 == weeks.len() {
+            weeks.push([None; 7]);
+        }
+
+        weeks[cell / 7][cell % 7] = Some(day);
+    }
+
+    weeks
+}
+
+// the same for everyone playing on the same day
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyChallenge {
+    pub date: Date,
+    pub seed: u64,
+    pub pack: String,
+    // names of the modifiers added to the ones always running
+    pub modifiers: Vec<String>,
+    // how fast the run starts, the same as `--difficulty`
+    pub difficulty: u8
+}
+
+impl DailyChallenge {
+    pub const MODIFIER_COUNT: usize = 3;
+    pub const MAX_DIFFICULTY: u8 = 5;
+
+    // `packs` and `modifiers` have to be in the same order for everyone
+    pub fn new(date: Date, packs: &[String], modifiers: &[String]) -> Self {
+        let seed = day_seed(date);
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let pack = seq::index::sample(&mut rng, packs.len().max(1), 1).index(0);
+        let mut picked = seq::index::sample(&mut rng, modifiers.len(), DailyChallenge::MODIFIER_COUNT.min(modifiers.len())).into_vec();
+        picked.sort();
+        let difficulty = rng.gen_range(0 ..= DailyChallenge::MAX_DIFFICULTY);
+
+        DailyChallenge {
+            date,
+            seed,
+            pack: packs.get(pack).cloned().unwrap_or_default(),
+            modifiers: picked.into_iter().map(|index| modifiers[index].clone()).collect(),
+            difficulty
+        }
+    }
+}
+
+// splitmix64 of the day, neighbouring days get unrelated seeds
+pub fn day_seed(date: Date) -> u64 {
+    let mut seed = (date.days() as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    seed = (seed ^ (seed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    seed = (seed ^ (seed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    seed ^ (seed >> 31)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyResult {
+    pub date: Date,
+    pub profile: String,
+    pub score: f32
+}
+
+// ranked daily results, one per profile and day
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DailyLog {
+    pub results: Vec<DailyResult>
+}
+
+impl DailyLog {
+    pub fn has_ranked(&self, date: Date, profile: &str) -> bool {
+        self.results.iter().any(|result| result.date == date && result.profile == profile)
+    }
+
+    // the attempts after the first one of the day aren't ranked, returns whether this one was
+    pub fn record(&mut self, result: DailyResult) -> bool {
+        if self.has_ranked(result.date, &result.profile) {
+            return false;
+        }
+
+        self.results.push(result);
+        true
+    }
+
+    // ranked attempts are recorded when they start, so quitting doesn't give another one
+    pub fn set_score(&mut self, date: Date, profile: &str, score: f32) {
+        if let Some(result) = self.results.iter_mut().find(|result| result.date == date && result.profile == profile) {
+            result.score = score;
+        }
+    }
+
+    // best first
+    pub fn day_results(&self, date: Date) -> Vec<&DailyResult> {
+        let mut results = self.results.iter().filter(|result| result.date == date).collect::<Vec<&DailyResult>>();
+        results.sort_by(|first, second| second.score.total_cmp(&first.score));
+
+        results
+    }
+
+    pub fn history(&self, profile: &str) -> BTreeMap<Date, f32> {
+        self.results.iter().filter(|result| result.profile == profile).map(|result| (result.date, result.score)).collect()
+    }
+
+    // `date<TAB>profile<TAB>score` lines
+    pub fn serialize(&self) -> String {
+        self.results.iter().map(|result| format!("{}\t{}\t{:.2}\n", result.date, result.profile, result.score)).collect()
+    }
+
+    // broken lines are skipped
+    pub fn parse(text: &str) -> Self {
+        let results = text.lines().
+            filter_map(|line| {
+                let mut fields = line.split('\t');
+                let date = Date::parse(fields.next()?)?;
+                let profile = fields.next()?.to_string();
+                let score = fields.next()?.parse().ok()?;
+
+                Some(DailyResult { date, profile, score })
+            }).
+            collect();
+
+        DailyLog { results }
+    }
+}
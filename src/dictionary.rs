@@ -0,0 +1,162 @@
+use std::collections::{ BTreeMap, HashMap, HashSet };
+use std::fmt;
+
+use crate::vocabulary;
+
+// besides the letters, these are the only keys the game turns into input
+pub const TYPABLE_SYMBOLS: &str = "- ";
+
+pub fn is_typable(letter: char) -> bool {
+    letter.is_ascii_alphabetic() || TYPABLE_SYMBOLS.contains(letter)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Duplicate {
+    pub word: String,
+    // every line the word is on, counted from 1
+    pub lines: Vec<usize>
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Untypable {
+    pub line: usize,
+    pub word: String,
+    pub letters: Vec<char>
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Report {
+    pub lines: usize,
+    // distinct words the game can use, the answers of the two column lines
+    pub words: usize,
+    pub pairs: usize,
+    pub duplicates: Vec<Duplicate>,
+    pub crlf_lines: usize,
+    pub blank_lines: Vec<usize>,
+    // two column lines with an empty prompt or answer
+    pub broken_pairs: Vec<usize>,
+    pub untypable: Vec<Untypable>,
+    // word length -> number of distinct words
+    pub length_distribution: BTreeMap<usize, usize>
+}
+
+impl Report {
+    pub fn is_clean(&self) -> bool {
+        self.duplicates.is_empty() && self.crlf_lines == 0 && self.blank_lines.is_empty() && self.broken_pairs.is_empty() && self.untypable.is_empty()
+    }
+}
+
+// longest bar of the length histogram
+const HISTOGRAM_WIDTH: usize = 40;
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} lines, {} usable words, {} of them with a prompt", self.lines, self.words, self.pairs)?;
+
+        writeln!(f, "duplicates: {}", self.duplicates.len())?;
+        for duplicate in &self.duplicates {
+            let lines = duplicate.lines.iter().map(|line| line.to_string()).collect::<Vec<String>>();
+            writeln!(f, "  '{}' on lines {}", duplicate.word, lines.join(", "))?;
+        }
+
+        writeln!(f, "CRLF line endings: {}", self.crlf_lines)?;
+
+        writeln!(f, "blank lines: {}", self.blank_lines.len())?;
+        if !self.blank_lines.is_empty() {
+            let blank_lines = self.blank_lines.iter().map(|line| line.to_string()).collect::<Vec<String>>();
+            writeln!(f, "  lines {}", blank_lines.join(", "))?;
+        }
+
+        writeln!(f, "pairs with an empty column: {}", self.broken_pairs.len())?;
+        if !self.broken_pairs.is_empty() {
+            let broken_pairs = self.broken_pairs.iter().map(|line| line.to_string()).collect::<Vec<String>>();
+            writeln!(f, "  lines {}", broken_pairs.join(", "))?;
+        }
+
+        writeln!(f, "words with untypable characters: {}", self.untypable.len())?;
+        for untypable in &self.untypable {
+            let letters = untypable.letters.iter().map(|letter| format!("{:?}", letter)).collect::<Vec<String>>();
+            writeln!(f, "  line {}: '{}' ({})", untypable.line, untypable.word, letters.join(" "))?;
+        }
+
+        writeln!(f, "length distribution:")?;
+        let most = self.length_distribution.values().copied().max().unwrap_or(1);
+        for (length, count) in &self.length_distribution {
+            let bar = "#".repeat((count * HISTOGRAM_WIDTH).div_ceil(most));
+            writeln!(f, "  {:>3}: {:>5} {}", length, count, bar)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn validate(text: &str) -> Report {
+    let mut report = Report::default();
+    let mut seen: HashMap<&str, Vec<usize>> = HashMap::new();
+    let mut order = Vec::new();
+
+    // the newline after the last word doesn't start another line
+    let body = text.strip_suffix('\n').unwrap_or(text);
+    let lines = if text.is_empty() { Vec::new() } else { body.split('\n').collect::<Vec<&str>>() };
+
+    for (index, line) in lines.into_iter().enumerate() {
+        let number = index + 1;
+        report.lines += 1;
+
+        if line.ends_with('\r') {
+            report.crlf_lines += 1;
+        }
+
+        let (prompt, word) = vocabulary::split_line(line);
+
+        if prompt.is_none() && word.is_empty() {
+            report.blank_lines.push(number);
+            continue;
+        }
+
+        if prompt.is_some_and(|prompt| prompt.is_empty()) || word.is_empty() {
+            report.broken_pairs.push(number);
+            continue;
+        }
+
+        let letters = word.chars().filter(|letter| !is_typable(*letter)).collect::<Vec<char>>();
+        if !letters.is_empty() {
+            report.untypable.push(Untypable { line: number, word: word.to_string(), letters });
+            continue;
+        }
+
+        let lines = seen.entry(word).or_default();
+        if lines.is_empty() {
+            order.push(word);
+            report.pairs += prompt.is_some() as usize;
+            *report.length_distribution.entry(word.chars().count()).or_default() += 1;
+        }
+        lines.push(number);
+    }
+
+    report.words = order.len();
+    report.duplicates = order.iter().
+        filter(|word| seen[*word].len() > 1).
+        map(|word| Duplicate { word: word.to_string(), lines: seen[*word].clone() }).
+        collect();
+
+    report
+}
+
+// the usable words in their first order, one per line with unix line endings,
+// a word keeps the prompt of its first line
+pub fn normalize(text: &str) -> String {
+    let mut seen = HashSet::new();
+    let lines = text.lines().
+        map(vocabulary::split_line).
+        filter(|(prompt, word)| prompt.is_none_or(|prompt| !prompt.is_empty()) && !word.is_empty() && word.chars().all(is_typable)).
+        filter(|(_, word)| seen.insert(*word)).
+        collect::<Vec<(Option<&str>, &str)>>();
+
+    lines.iter().
+        map(|(prompt, word)| match prompt {
+            Some(prompt) => format!("{}{}{}\n", prompt, vocabulary::COLUMN_SEPARATOR, word),
+            None => format!("{}\n", word)
+        }).
+        collect()
+}
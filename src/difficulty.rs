@@ -0,0 +1,42 @@
+use crate::scoring::rare_letters_count;
+
+// letters typed with the same finger of a qwerty keyboard, left pinky to right pinky
+const FINGERS: [&str; 8] = ["qaz", "wsx", "edc", "rfvtgb", "yhnujm", "ik", "ol", "p"];
+// the first four fingers belong to the left hand
+const LEFT_HAND_FINGERS: usize = 4;
+
+// the difficulty is counted in letters, every trait below adds a part of a letter
+pub const RARE_LETTER_WEIGHT: f32 = 0.5;
+pub const SAME_HAND_WEIGHT: f32 = 0.25;
+pub const AWKWARD_BIGRAM_WEIGHT: f32 = 1.0;
+
+fn finger(letter: char) -> Option<usize> {
+    let letter = letter.to_ascii_lowercase();
+    FINGERS.iter().position(|letters| letters.contains(letter))
+}
+
+fn letter_pairs(label: &str) -> Vec<(usize, usize)> {
+    let fingers = label.chars().map(finger).collect::<Vec<Option<usize>>>();
+
+    fingers.windows(2).filter_map(|pair| Some((pair[0]?, pair[1]?))).collect()
+}
+
+// neighbouring letters typed by the same hand
+pub fn same_hand_pairs(label: &str) -> usize {
+    letter_pairs(label).iter().filter(|(first, second)| (*first < LEFT_HAND_FINGERS) == (*second < LEFT_HAND_FINGERS)).count()
+}
+
+// neighbouring letters typed by the same finger, double letters don't count
+pub fn awkward_bigrams(label: &str) -> usize {
+    let letters = label.to_lowercase().chars().collect::<Vec<char>>();
+
+    letters.windows(2).filter(|pair| pair[0] != pair[1] && finger(pair[0]).is_some() && finger(pair[0]) == finger(pair[1])).count()
+}
+
+// roughly the number of letters of an ordinary word which is as hard to type
+pub fn word_difficulty(label: &str) -> f32 {
+    label.chars().count() as f32 +
+    rare_letters_count(label) as f32 * RARE_LETTER_WEIGHT +
+    same_hand_pairs(label) as f32 * SAME_HAND_WEIGHT +
+    awkward_bigrams(label) as f32 * AWKWARD_BIGRAM_WEIGHT
+}
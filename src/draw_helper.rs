@@ -1,7 +1,7 @@
 use ggez:: {graphics, Context };
 use ggez::mint::Point2;
 
-pub fn format_scoreboard(scoreboard: &Vec<String>) -> String {
+pub fn format_scoreboard(scoreboard: &[String]) -> String {
     let mut result = String::new();
 
     for (index,score) in scoreboard.iter().enumerate()
@@ -30,6 +30,23 @@ pub fn draw_text_background(text_pos: Point2<f32>, text_width: f32, text_height:
     graphics::draw(ctx, &background_mesh, graphics::DrawParam::default()).unwrap();
 }
 
+// progress is in [0.0, 1.0], the filled part starts from the left
+pub fn draw_progress_bar(top_left: Point2<f32>, width: f32, height: f32, progress: f32, color: graphics::Color, ctx: &mut Context) {
+    let filled = graphics::Rect::new(top_left.x, top_left.y, width * progress.clamp(0.0, 1.0), height);
+    draw_rect(filled, color, ctx);
+}
+
+pub fn draw_rect(rect: graphics::Rect, color: graphics::Color, ctx: &mut Context) {
+    let draw_mode = graphics::DrawMode::Fill(graphics::FillOptions::DEFAULT);
+    let rect_mesh = graphics::MeshBuilder::new().
+        rectangle(draw_mode, rect, color).
+        unwrap().
+        build(ctx).
+        unwrap();
+
+    graphics::draw(ctx, &rect_mesh, graphics::DrawParam::default()).unwrap();
+}
+
 pub fn translate(pos: &mut Point2<f32>, trans: &Point2<f32>) {
     pos.x += trans.x;
     pos.y += trans.y;
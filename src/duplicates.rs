@@ -0,0 +1,85 @@
+use ggez::graphics::Rect;
+
+use crate::entities::Word;
+
+// what happens when the input matches several words on the screen
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum DuplicatePolicy {
+    // only the word closest to escaping is hit
+    #[default]
+    NearestToEdge,
+    // every matching word is hit at once
+    AllDie,
+    // a word is never spawned while the same word is on the screen
+    NoDuplicates
+}
+
+impl DuplicatePolicy {
+    pub const ALL: [DuplicatePolicy; 3] = [
+        DuplicatePolicy::NearestToEdge,
+        DuplicatePolicy::AllDie,
+        DuplicatePolicy::NoDuplicates
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            DuplicatePolicy::NearestToEdge => "nearest wins",
+            DuplicatePolicy::AllDie => "all die",
+            DuplicatePolicy::NoDuplicates => "no duplicates"
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = DuplicatePolicy::ALL.iter().position(|policy| policy == self).unwrap_or(0);
+        DuplicatePolicy::ALL[(index + 1) % DuplicatePolicy::ALL.len()]
+    }
+
+    // indices of the words hit by the input, `word_rects` are the bounding rects of `words`
+    pub fn targets(&self, words: &[Word], word_rects: &[Rect], input: &str, field: Rect) -> Vec<usize> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        let matching = words.iter().
+            zip(word_rects).
+            enumerate().
+            filter(|(_, (word, _))| !word.is_typed && word.label() == input);
+
+        match self {
+            DuplicatePolicy::AllDie => matching.map(|(index, _)| index).collect(),
+            // split words can still bring a duplicate on the screen, so pick the nearest one here too
+            DuplicatePolicy::NearestToEdge | DuplicatePolicy::NoDuplicates => nearest_to_edge(matching, field).into_iter().collect()
+        }
+    }
+
+    // the word a partial input is aimed at, the one closest to escaping like with the default policy
+    pub fn typed_word(words: &[Word], word_rects: &[Rect], typed: &str, field: Rect) -> Option<usize> {
+        if typed.is_empty() {
+            return None;
+        }
+
+        let matching = words.iter().
+            zip(word_rects).
+            enumerate().
+            filter(|(_, (word, _))| !word.is_typed && word.label().starts_with(typed));
+
+        nearest_to_edge(matching, field)
+    }
+
+    pub fn allows_spawn(&self, words: &[Word], label: &str) -> bool {
+        match self {
+            DuplicatePolicy::NoDuplicates => !words.iter().any(|word| !word.is_typed && word.label() == label),
+            _ => true
+        }
+    }
+}
+
+fn nearest_to_edge<'a>(words: impl Iterator<Item = (usize, (&'a Word, &'a Rect))>, field: Rect) -> Option<usize> {
+    words.
+        min_by(|(_, (first, first_rect)), (_, (second, second_rect))| {
+            let first_distance = first.heading().distance_to_escape(**first_rect, field);
+            let second_distance = second.heading().distance_to_escape(**second_rect, field);
+            first_distance.total_cmp(&second_distance)
+        }).
+        map(|(index, _)| index)
+}
@@ -3,47 +3,275 @@ use ggez::mint::{ Point2, Vector2 };
 
 use rand::Rng;
 use rand::rngs::ThreadRng;
+use rand::seq::SliceRandom;
 
 use crate::assets::Sprite;
+use crate::movement::{ Movement, Linear, Heading };
+use crate::difficulty;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WordKind {
+    Normal,
+    // has to be typed once for every hit left
+    Armored { hits_left: u32 },
+    // breaks into two shorter words when typed
+    Splitting,
+    // pays extra cash and costs no life when missed
+    Golden,
+    // a whole sentence which takes several lives when it escapes
+    Boss { lives: u32 }
+}
+
+impl WordKind {
+    pub const ARMOR_HITS: u32 = 2;
+    pub const GOLDEN_CASH_MULTIPLIER: f32 = 3.0;
+    pub const MIN_SPLIT_LENGTH: usize = 4;
+
+    // boss words are spawned on their own schedule, so they are never picked here
+    pub fn pick<R: Rng>(label: &str, rng: &mut R) -> Self {
+        let percentage: u8 = rng.gen_range(0 .. 100);
+
+        match percentage {
+            0 ..= 9 => WordKind::Armored { hits_left: WordKind::ARMOR_HITS },
+            10 ..= 19 if label.chars().count() >= WordKind::MIN_SPLIT_LENGTH => WordKind::Splitting,
+            20 ..= 24 => WordKind::Golden,
+            _ => WordKind::Normal
+        }
+    }
+
+    pub fn color(&self) -> graphics::Color {
+        match self {
+            WordKind::Normal => graphics::Color::from_rgb(255, 255, 255),
+            WordKind::Armored { hits_left } if *hits_left > 1 => graphics::Color::from_rgb(110, 140, 190),
+            WordKind::Armored { .. } => graphics::Color::from_rgb(170, 190, 220),
+            WordKind::Splitting => graphics::Color::from_rgb(255, 150, 50),
+            WordKind::Golden => graphics::Color::from_rgb(255, 215, 0),
+            WordKind::Boss { .. } => graphics::Color::from_rgb(230, 40, 40)
+        }
+    }
+}
+
+// whether the player can see a word, fading words are still shown
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Visibility {
+    #[default]
+    Shown,
+    // shown until the word is this many seconds old, then only its hitbox is left
+    Flash { seconds: f32 },
+    Hidden
+}
 
 #[derive(Debug)]
 pub struct Word {
     pub pos: Point2<f32>,
     pub is_typed: bool,
+    pub kind: WordKind,
     pub is_color_changing: bool,
+    pub is_reversed: bool,
+    // seconds until the word is completely invisible
+    pub fade_time: Option<f32>,
+    pub visibility_state: Visibility,
     real_pos: Point2<f32>,
     rng: ThreadRng,
     label: String,
-    velocity: Vector2<f32>,
+    // letters of the label in a random order, they get sorted out while typing
+    scrambled: Option<Vec<char>>,
+    shown_label: String,
+    // shown instead of the label, which stays the answer the player has to type
+    prompt: Option<String>,
+    difficulty: f32,
+    age: f32,
+    speed: f32,
+    movement: Box<dyn Movement>,
     sprite: Box<dyn Sprite>
 }
 
 impl Word {
+    pub const COLOR_CHANGING_MULTIPLIER: f32 = 2.0;
+
     pub fn new(label: &str, pos: Point2<f32>, speed: f32, sprite: Box<dyn Sprite>, is_color_changing: bool) -> GameResult<Self> {
         let label = String::from(label);
 
         Ok(Word {
             pos,
             is_typed: false,
+            kind: WordKind::Normal,
             is_color_changing,
+            is_reversed: false,
+            fade_time: None,
+            visibility_state: Visibility::Shown,
             real_pos: pos,
             rng: rand::thread_rng(),
+            shown_label: label.clone(),
+            difficulty: difficulty::word_difficulty(&label),
             label,
-            velocity: Vector2 { x: speed, y: 0.0 },
+            prompt: None,
+            scrambled: None,
+            age: 0.0,
+            speed,
+            movement: Box::new(Linear { velocity: Vector2 { x: speed, y: 0.0 } }),
             sprite
         })
     }
 
+    // words move left to right in a straight line until another movement is set
+    pub fn set_movement(&mut self, movement: Box<dyn Movement>) {
+        self.movement = movement;
+    }
+
+    pub fn heading(&self) -> Heading {
+        self.movement.heading()
+    }
+
     pub fn label(&self) -> &str {
         self.label.as_str()
     }
 
+    pub fn prompt(&self) -> Option<&str> {
+        self.prompt.as_deref()
+    }
+
+    pub fn set_prompt(&mut self, prompt: &str) {
+        self.prompt = Some(prompt.to_string());
+        self.sprite.set_label(prompt);
+        self.shown_label = prompt.to_string();
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn difficulty(&self) -> f32 {
+        self.difficulty
+    }
+
+    // called when the word is typed, returns true when it is destroyed
+    pub fn hit(&mut self) -> bool {
+        if let WordKind::Armored { hits_left } = &mut self.kind {
+            *hits_left = hits_left.saturating_sub(1);
+
+            if *hits_left > 0 {
+                return false;
+            }
+        }
+
+        self.is_typed = true;
+        true
+    }
+
+    // the two halves a splitting word breaks into
+    pub fn split_labels(&self) -> Option<(String, String)> {
+        let length = self.label.chars().count();
+
+        // the halves of an answer would have no prompt
+        if self.kind != WordKind::Splitting || length < WordKind::MIN_SPLIT_LENGTH || self.prompt.is_some() {
+            return None;
+        }
+
+        let first = self.label.chars().take(length / 2).collect();
+        let second = self.label.chars().skip(length / 2).collect();
+
+        Some((first, second))
+    }
+
+    // lives lost when the word reaches the end of the screen
+    pub fn escape_cost(&self) -> u32 {
+        match self.kind {
+            WordKind::Golden => 0,
+            WordKind::Boss { lives } => lives,
+            _ => 1
+        }
+    }
+
+    pub fn cash_multiplier(&self) -> f32 {
+        match self.kind {
+            WordKind::Golden => WordKind::GOLDEN_CASH_MULTIPLIER,
+            _ => 1.0
+        }
+    }
+
+    // the text on the screen, which may differ from the label the player has to type
+    pub fn shown_label(&self) -> &str {
+        self.shown_label.as_str()
+    }
+
+    // takes the rng of the game, so seeded runs scramble the same way
+    pub fn scramble<R: Rng>(&mut self, rng: &mut R) {
+        let mut letters = self.label.chars().collect::<Vec<char>>();
+        letters.shuffle(rng);
+        self.scrambled = Some(letters);
+    }
+
+    // refreshes the shown text for the current user input
+    pub fn update_shown_label(&mut self, current_input: &str) {
+        let shown_label = self.compute_shown_label(current_input);
+
+        if shown_label != self.shown_label {
+            self.sprite.set_label(&shown_label);
+            self.shown_label = shown_label;
+        }
+    }
+
+    fn compute_shown_label(&self, current_input: &str) -> String {
+        if let Some(prompt) = &self.prompt {
+            return prompt.clone();
+        }
+
+        if let Some(scrambled) = &self.scrambled {
+            // the correctly typed prefix is unscrambled, the rest keeps its order
+            let typed = self.label.chars().zip(current_input.chars()).take_while(|(expected, actual)| expected == actual).count();
+            let prefix = self.label.chars().take(typed).collect::<String>();
+            let mut rest = scrambled.clone();
+
+            for letter in prefix.chars() {
+                if let Some(index) = rest.iter().position(|&x| x == letter) {
+                    rest.remove(index);
+                }
+            }
+
+            return prefix + &rest.into_iter().collect::<String>();
+        }
+
+        if self.is_reversed {
+            return self.label.chars().rev().collect();
+        }
+
+        self.label.clone()
+    }
+
+    // 1.0 is fully visible, 0.0 is invisible
+    pub fn visibility(&self) -> f32 {
+        if self.is_hidden() {
+            return 0.0;
+        }
+
+        match self.fade_time {
+            Some(fade_time) if fade_time > 0.0 => (1.0 - self.age / fade_time).clamp(0.0, 1.0),
+            Some(_) => 0.0,
+            None => 1.0
+        }
+    }
+
     pub fn update(&mut self, seconds: f32) {
-        self.pos.x += self.velocity.x * seconds;
-        self.pos.y += self.velocity.y * seconds;
+        let displacement = self.movement.step(self.age, seconds);
+
+        self.pos.x += displacement.x;
+        self.pos.y += displacement.y;
+
+        self.real_pos.x += displacement.x;
+        self.real_pos.y += displacement.y;
+
+        self.age += seconds;
+
+        if let Visibility::Flash { seconds } = self.visibility_state {
+            if self.age >= seconds {
+                self.visibility_state = Visibility::Hidden;
+            }
+        }
+    }
 
-        self.real_pos.x += self.velocity.x * seconds;
-        self.real_pos.y += self.velocity.y * seconds;
+    pub fn is_hidden(&self) -> bool {
+        self.visibility_state == Visibility::Hidden
     }
 
     pub fn translate(&mut self, translation: Point2<f32>) {
@@ -56,28 +284,28 @@ impl Word {
         self.pos.y = self.real_pos.y;
     }
 
-    pub fn get_reward(&mut self) -> f32 {
-        let color_multi = {
-            if self.is_color_changing {
-                2.0;
-            }
-
-            1.0
-        };
+    // base reward before any scoring rule is applied
+    pub fn get_reward(&self) -> f32 {
+        let color_multi = if self.is_color_changing { Word::COLOR_CHANGING_MULTIPLIER } else { 1.0 };
 
-        self.velocity.x * color_multi * (self.label.len() as f32) / 100.0
+        self.speed * color_multi * self.difficulty / 100.0
     }
 
     pub fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+        let alpha = (self.visibility() * 255.0) as u8;
+
         if self.is_color_changing {
             self.sprite.draw(self.pos,
-                       graphics::Color::from_rgb(
+                       graphics::Color::from_rgba(
                                 self.rng.gen_range(0 ..= 255),
                                 self.rng.gen_range(0 ..= 255),
-                                self.rng.gen_range(0 ..= 255)), ctx)
+                                self.rng.gen_range(0 ..= 255),
+                                alpha), ctx)
         }
         else {
-            self.sprite.draw(self.pos, graphics::Color::from_rgb(255, 255, 255), ctx)
+            let mut color = self.kind.color();
+            color.a = self.visibility();
+            self.sprite.draw(self.pos, color, ctx)
         }
     }
 
@@ -90,4 +318,40 @@ impl Word {
 
         graphics::Rect::new(left, top, right - left, bottom - top)
     }
-}
\ No newline at end of file
+}
+
+// floating text which rises from where a word was typed and fades out
+#[derive(Debug)]
+pub struct Popup {
+    pub pos: Point2<f32>,
+    age: f32,
+    sprite: Box<dyn Sprite>
+}
+
+impl Popup {
+    pub const LIFETIME: f32 = 1.2;
+    pub const RISE_SPEED: f32 = 40.0;
+
+    pub fn new(pos: Point2<f32>, sprite: Box<dyn Sprite>) -> Self {
+        Popup {
+            pos,
+            age: 0.0,
+            sprite
+        }
+    }
+
+    pub fn update(&mut self, seconds: f32) {
+        self.pos.y -= Popup::RISE_SPEED * seconds;
+        self.age += seconds;
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.age >= Popup::LIFETIME
+    }
+
+    pub fn draw(&mut self, color: graphics::Color, ctx: &mut Context) -> GameResult<()> {
+        let mut color = color;
+        color.a = (1.0 - self.age / Popup::LIFETIME).clamp(0.0, 1.0);
+        self.sprite.draw(self.pos, color, ctx)
+    }
+}
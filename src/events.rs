@@ -0,0 +1,244 @@
+use rand::Rng;
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use crate::game_state::GameState;
+use crate::modes::GameMode;
+
+// `mode: event, event` lines, the modes which aren't listed get every event
+pub const EVENTS_PATH: &str = "/events.txt";
+
+// a temporary change of the whole game, announced a few seconds before it starts
+pub trait WorldEvent: Debug {
+    fn name(&self) -> &str;
+    // shown in the warning
+    fn description(&self) -> &str;
+    fn duration(&self) -> f32;
+
+    fn on_start(&self, state: &mut GameState);
+    fn on_end(&self, state: &mut GameState);
+}
+
+// words come more often
+#[derive(Debug)]
+pub struct RushHour {
+    pub spawn_rate: f32
+}
+
+impl WorldEvent for RushHour {
+    fn name(&self) -> &str {
+        "Rush Hour"
+    }
+
+    fn description(&self) -> &str {
+        "Twice as many words"
+    }
+
+    fn duration(&self) -> f32 {
+        10.0
+    }
+
+    fn on_start(&self, state: &mut GameState) {
+        state.spawn_rate = self.spawn_rate;
+    }
+
+    fn on_end(&self, state: &mut GameState) {
+        state.spawn_rate = 1.0;
+    }
+}
+
+// every word pays more cash
+#[derive(Debug)]
+pub struct GoldRush {
+    pub cash_multiplier: f32
+}
+
+impl WorldEvent for GoldRush {
+    fn name(&self) -> &str {
+        "Gold Rush"
+    }
+
+    fn description(&self) -> &str {
+        "Every word pays double cash"
+    }
+
+    fn duration(&self) -> f32 {
+        12.0
+    }
+
+    fn on_start(&self, state: &mut GameState) {
+        state.cash_multiplier = self.cash_multiplier;
+    }
+
+    fn on_end(&self, state: &mut GameState) {
+        state.cash_multiplier = 1.0;
+    }
+}
+
+// only the typed prefix of the words stays visible
+#[derive(Debug)]
+pub struct Blackout;
+
+impl WorldEvent for Blackout {
+    fn name(&self) -> &str {
+        "Blackout"
+    }
+
+    fn description(&self) -> &str {
+        "The lights go out, only what you type is visible"
+    }
+
+    fn duration(&self) -> f32 {
+        8.0
+    }
+
+    fn on_start(&self, state: &mut GameState) {
+        state.blackout = true;
+    }
+
+    fn on_end(&self, state: &mut GameState) {
+        state.blackout = false;
+    }
+}
+
+// the events allowed in every mode
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EventConfig {
+    pub modes: BTreeMap<String, Vec<String>>
+}
+
+impl EventConfig {
+    // lines starting with `#` are skipped, `none` switches the events of a mode off
+    pub fn parse(text: &str) -> Self {
+        let modes = text.lines().
+            map(|line| line.trim()).
+            filter(|line| !line.is_empty() && !line.starts_with('#')).
+            filter_map(|line| line.split_once(':')).
+            map(|(mode, events)| {
+                let events = events.split(',').
+                    map(|event| event.trim().to_string()).
+                    filter(|event| !event.is_empty() && event != "none").
+                    collect();
+
+                (mode.trim().to_string(), events)
+            }).
+            collect();
+
+        EventConfig { modes }
+    }
+
+    // `None` when the mode isn't configured
+    pub fn events(&self, mode: GameMode) -> Option<&[String]> {
+        self.modes.get(mode.id()).map(|events| events.as_slice())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Phase {
+    Warning,
+    Running
+}
+
+// one event at a time, with a quiet gap between two of them
+#[derive(Debug, Default)]
+pub struct EventScheduler {
+    events: Vec<Box<dyn WorldEvent>>,
+    time_until_next: f32,
+    // the announced or running event and the seconds left of its phase
+    current: Option<(usize, Phase, f32)>
+}
+
+impl EventScheduler {
+    pub const FIRST_EVENT: f32 = 30.0;
+    pub const MIN_GAP: f32 = 20.0;
+    pub const MAX_GAP: f32 = 40.0;
+    // seconds between the warning and the start of an event
+    pub const WARNING_TIME: f32 = 3.0;
+
+    pub fn new() -> Self {
+        EventScheduler {
+            events: Vec::new(),
+            time_until_next: EventScheduler::FIRST_EVENT,
+            current: None
+        }
+    }
+
+    pub fn with_defaults() -> Self {
+        let mut scheduler = EventScheduler::new();
+        scheduler.register(Box::new(RushHour { spawn_rate: 2.0 }));
+        scheduler.register(Box::new(GoldRush { cash_multiplier: 2.0 }));
+        scheduler.register(Box::new(Blackout));
+
+        scheduler
+    }
+
+    // the default events, or those configured for the mode
+    pub fn for_mode(mode: GameMode, config: &EventConfig) -> Self {
+        let mut scheduler = EventScheduler::with_defaults();
+
+        if let Some(names) = config.events(mode) {
+            scheduler.keep(names);
+        }
+
+        scheduler
+    }
+
+    pub fn register(&mut self, event: Box<dyn WorldEvent>) {
+        self.events.push(event);
+    }
+
+    // drops the events with other names, before the game starts
+    pub fn keep(&mut self, names: &[String]) {
+        self.events.retain(|event| names.iter().any(|name| name == event.name()));
+        self.current = None;
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.events.iter().map(|event| event.name().to_string()).collect()
+    }
+
+    // the announced event and the seconds until it starts
+    pub fn warning(&self) -> Option<(&dyn WorldEvent, f32)> {
+        match self.current {
+            Some((index, Phase::Warning, remaining)) => Some((self.events[index].as_ref(), remaining)),
+            _ => None
+        }
+    }
+
+    // the running event and the seconds until it ends
+    pub fn running(&self) -> Option<(&dyn WorldEvent, f32)> {
+        match self.current {
+            Some((index, Phase::Running, remaining)) => Some((self.events[index].as_ref(), remaining)),
+            _ => None
+        }
+    }
+
+    pub fn update(&mut self, seconds: f32, state: &mut GameState) {
+        if self.events.is_empty() {
+            return;
+        }
+
+        self.current = match self.current {
+            None => {
+                self.time_until_next -= seconds;
+
+                if self.time_until_next > 0.0 {
+                    return;
+                }
+
+                Some((state.rng.gen_range(0 .. self.events.len()), Phase::Warning, EventScheduler::WARNING_TIME))
+            },
+            Some((index, phase, remaining)) if remaining > seconds => Some((index, phase, remaining - seconds)),
+            Some((index, Phase::Warning, _)) => {
+                self.events[index].on_start(state);
+                Some((index, Phase::Running, self.events[index].duration()))
+            },
+            Some((index, Phase::Running, _)) => {
+                self.events[index].on_end(state);
+                self.time_until_next = state.rng.gen_range(EventScheduler::MIN_GAP .. EventScheduler::MAX_GAP);
+                None
+            }
+        };
+    }
+}
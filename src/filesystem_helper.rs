@@ -1,12 +1,33 @@
 use ggez:: { filesystem, Context };
 
 use std::io::{Read, Write};
+use std::path::PathBuf;
 use std::str;
-use std::mem::swap;
+
+use crate::word_packs::{ self, Manifest, WordPack };
+use crate::content_filter::WordTags;
+use crate::settings::Settings;
+use crate::training::TypingStats;
+use crate::review::MistakeLog;
+use crate::daily::DailyLog;
+use crate::levels::{ self, Level, CampaignProgress };
+use crate::events::{ self, EventConfig };
+
+const SCORES_PATH: &str = "/scoring.data";
+const LAST_PACK_PATH: &str = "/last_pack.data";
+const SETTINGS_PATH: &str = "/settings.data";
+const BLOCKLIST_PATH: &str = "/blocklist.txt";
+// kept apart from the scoreboards, so daily runs don't mix with the normal ones
+const DAILY_PATH: &str = "/daily.data";
+const PROFILE_PATH: &str = "/profile.data";
+const CAMPAIGN_PATH: &str = "/campaign.data";
+const TYPING_STATS_FILE: &str = "typing_stats.data";
+const MISTAKES_FILE: &str = "mistakes.data";
+const MISTAKES_CSV_FILE: &str = "mistakes.csv";
 
 pub fn read_file_by_lines(ctx: &Context, path: &str) -> Vec<String> {
     let file = filesystem::open(ctx, path);
-        
+
     if file.is_err() {
         panic!("Error with opening {}!", path);
     }
@@ -22,46 +43,237 @@ pub fn read_file_by_lines(ctx: &Context, path: &str) -> Vec<String> {
     words.iter().map(|x| x.to_string()).collect::<Vec<String>>()
 }
 
-pub fn save_score(ctx: &Context, username: String, score: f32, scoreboard_size: usize) -> Vec<String> {
-    let mut file;
-    if filesystem::exists(ctx, "/scoring.data") {
-        let mut scores = read_file_by_lines(ctx, "/scoring.data");
+fn read_to_string(ctx: &Context, path: &str) -> Option<String> {
+    let mut buffer = String::new();
+    filesystem::open(ctx, path).ok()?.read_to_string(&mut buffer).ok()?;
 
-        let mut new_line = format!("{} {:.2}", username, score);
-        let mut insert = false;
-        for line in scores.iter_mut() {
-            let split = line.split(" ").collect::<Vec<&str>>();
-            let saved_score = split[split.len() - 1].parse::<f32>().unwrap();
+    Some(buffer)
+}
 
-            if saved_score < score
-            {
-                insert = true;
-            }
+// packs from the resources and the user data directory, sorted by id,
+// a pack in the resources hides a user pack with the same id
+pub fn discover_packs(ctx: &Context) -> Vec<WordPack> {
+    let mut packs: Vec<WordPack> = Vec::new();
+    let directories = match filesystem::read_dir(ctx, word_packs::PACKS_DIR) {
+        Ok(directories) => directories,
+        Err(_) => return packs
+    };
 
-            if insert {
-                swap(line, &mut new_line);
-            }
+    for directory in directories {
+        let id = match directory.file_name().and_then(|name| name.to_str()) {
+            Some(id) => id.to_string(),
+            None => continue
+        };
+
+        if packs.iter().any(|pack| pack.id == id) {
+            continue;
         }
 
-        if scores.len() < scoreboard_size
-        {
-            scores.push(new_line);
+        // directories without a valid manifest aren't packs
+        let manifest = read_to_string(ctx, &WordPack::manifest_path(&id)).and_then(|text| Manifest::parse(&text).ok());
+
+        if let Some(manifest) = manifest {
+            let pack = WordPack { id, manifest };
+
+            // the word files are read with `read_file_by_lines` later, which gives up on missing or empty files
+            match pack.word_paths().into_iter().find(|path| read_to_string(ctx, path).is_none_or(|text| text.trim().is_empty())) {
+                Some(path) => eprintln!("Skipped the pack {}: {} is missing or empty", pack.id, path),
+                None => packs.push(pack)
+            }
         }
+    }
+
+    packs.sort_by(|first, second| first.id.cmp(&second.id));
+    packs
+}
+
+pub fn read_pack_words(ctx: &Context, pack: &WordPack) -> Vec<String> {
+    pack.word_paths().iter().flat_map(|path| read_file_by_lines(ctx, path)).collect()
+}
+
+// packs without a tags file have no tagged words
+pub fn read_word_tags(ctx: &Context, pack: &WordPack) -> WordTags {
+    pack.word_tags_path().
+        and_then(|path| read_to_string(ctx, &path)).
+        map(|text| WordTags::parse(&text)).
+        unwrap_or_default()
+}
+
+// without the player's own list, for runs which have to be the same for everyone
+pub fn read_shipped_blocklist(ctx: &Context) -> String {
+    read_to_string(ctx, BLOCKLIST_PATH).unwrap_or_default()
+}
+
+pub fn read_blocklist(ctx: &Context) -> String {
+    let mut blocklist = read_shipped_blocklist(ctx);
+
+    // the player's own list adds to the one shipped with the game
+    let user_blocklist = filesystem::user_data_dir(ctx).join(BLOCKLIST_PATH.trim_start_matches('/'));
+    if let Ok(user_blocklist) = std::fs::read_to_string(user_blocklist) {
+        blocklist.push('\n');
+        blocklist.push_str(&user_blocklist);
+    }
+
+    blocklist
+}
+
+pub fn read_event_config(ctx: &Context) -> EventConfig {
+    read_to_string(ctx, events::EVENTS_PATH).map(|text| EventConfig::parse(&text)).unwrap_or_default()
+}
+
+pub fn load_settings(ctx: &Context) -> Settings {
+    read_to_string(ctx, SETTINGS_PATH).map(|text| Settings::parse(&text)).unwrap_or_default()
+}
+
+pub fn save_settings(ctx: &Context, settings: &Settings) {
+    if let Ok(mut file) = filesystem::create(ctx, SETTINGS_PATH) {
+        let _ = file.write(settings.serialize().as_bytes());
+    }
+}
+
+// the player's progress is kept in the user data directory next to their own packs
+fn read_user_data(ctx: &Context, file: &str) -> Option<String> {
+    std::fs::read_to_string(filesystem::user_data_dir(ctx).join(file)).ok()
+}
+
+fn write_user_data(ctx: &Context, file: &str, contents: &str) -> std::io::Result<PathBuf> {
+    let directory = filesystem::user_data_dir(ctx);
+    std::fs::create_dir_all(directory)?;
+
+    let path = directory.join(file);
+    std::fs::write(&path, contents)?;
+
+    Ok(path)
+}
+
+pub fn load_typing_stats(ctx: &Context) -> TypingStats {
+    read_user_data(ctx, TYPING_STATS_FILE).map(|text| TypingStats::parse(&text)).unwrap_or_default()
+}
+
+pub fn save_typing_stats(ctx: &Context, stats: &TypingStats) {
+    let _ = write_user_data(ctx, TYPING_STATS_FILE, &stats.serialize());
+}
+
+pub fn load_mistakes(ctx: &Context) -> MistakeLog {
+    read_user_data(ctx, MISTAKES_FILE).map(|text| MistakeLog::parse(&text)).unwrap_or_default()
+}
+
+pub fn save_mistakes(ctx: &Context, log: &MistakeLog) {
+    let _ = write_user_data(ctx, MISTAKES_FILE, &log.serialize());
+}
+
+// returns where the file was written
+pub fn export_mistakes(ctx: &Context, log: &MistakeLog) -> std::io::Result<PathBuf> {
+    write_user_data(ctx, MISTAKES_CSV_FILE, &log.to_csv())
+}
+
+pub fn load_daily_log(ctx: &Context) -> DailyLog {
+    read_to_string(ctx, DAILY_PATH).map(|text| DailyLog::parse(&text)).unwrap_or_default()
+}
+
+pub fn save_daily_log(ctx: &Context, log: &DailyLog) {
+    if let Ok(mut file) = filesystem::create(ctx, DAILY_PATH) {
+        let _ = file.write(log.serialize().as_bytes());
+    }
+}
+
+// the campaign levels in the order of their file names, broken levels are reported and skipped
+pub fn discover_levels(ctx: &Context) -> Vec<Level> {
+    let files = match filesystem::read_dir(ctx, levels::LEVELS_DIR) {
+        Ok(files) => files,
+        Err(_) => return Vec::new()
+    };
+
+    let mut found = files.filter(|file| file.extension().is_some_and(|extension| extension == levels::LEVEL_EXTENSION)).
+        filter_map(|file| {
+            let id = file.file_stem()?.to_str()?.to_string();
+            let text = read_to_string(ctx, file.to_str()?)?;
+
+            match Level::parse(&id, &text) {
+                Ok(level) => Some(level),
+                Err(error) => {
+                    eprintln!("Skipped the level {}: {}", file.display(), error);
+                    None
+                }
+            }
+        }).
+        collect::<Vec<Level>>();
+
+    found.sort_by(|first, second| first.id.cmp(&second.id));
+    found.dedup_by(|first, second| first.id == second.id);
+    found
+}
+
+pub fn load_campaign(ctx: &Context) -> CampaignProgress {
+    read_to_string(ctx, CAMPAIGN_PATH).map(|text| CampaignProgress::parse(&text)).unwrap_or_default()
+}
 
-        file = filesystem::create(ctx, "/scoring.data").unwrap();
+pub fn save_campaign(ctx: &Context, progress: &CampaignProgress) {
+    if let Ok(mut file) = filesystem::create(ctx, CAMPAIGN_PATH) {
+        let _ = file.write(progress.serialize().as_bytes());
+    }
+}
 
-        let _ = file.write(scores.join("\n").as_bytes());
+pub fn load_last_pack(ctx: &Context) -> Option<String> {
+    read_to_string(ctx, LAST_PACK_PATH).map(|id| id.trim().to_string())
+}
 
-        return scores;
+pub fn save_last_pack(ctx: &Context, id: &str) {
+    if let Ok(mut file) = filesystem::create(ctx, LAST_PACK_PATH) {
+        let _ = file.write(id.as_bytes());
     }
-    else {
-        file = filesystem::create(ctx, "/scoring.data").unwrap();
+}
+
+pub fn load_profile(ctx: &Context) -> Option<String> {
+    read_to_string(ctx, PROFILE_PATH).map(|name| name.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+pub fn save_profile(ctx: &Context, name: &str) {
+    if let Ok(mut file) = filesystem::create(ctx, PROFILE_PATH) {
+        let _ = file.write(name.as_bytes());
     }
+}
+
+// score lines are `pack<TAB>username score`, lines without a pack were saved before there were packs
+fn split_score_line(line: &str) -> (&str, &str) {
+    line.split_once('\t').unwrap_or((word_packs::DEFAULT_PACK, line))
+}
 
-    let new_score = format!("{} {:.2}", username, score);
-    let _ = file.write(new_score.as_bytes());
-    let mut result = Vec::new();
-    result.push(new_score);
+fn entry_score(entry: &str) -> f32 {
+    entry.rsplit(' ').next().and_then(|score| score.parse().ok()).unwrap_or(0.0)
+}
+
+// `username score` entries of a single pack, best first
+pub fn pack_scores(lines: &[String], pack: &str) -> Vec<String> {
+    lines.iter().
+        map(|line| split_score_line(line)).
+        filter(|(line_pack, _)| *line_pack == pack).
+        map(|(_, entry)| entry.to_string()).
+        collect()
+}
+
+// the score lines after adding the new score to its pack's scoreboard
+pub fn insert_score(lines: &[String], pack: &str, username: &str, score: f32, scoreboard_size: usize) -> Vec<String> {
+    let mut scoreboard = pack_scores(lines, pack);
+    let position = scoreboard.iter().position(|entry| entry_score(entry) < score).unwrap_or(scoreboard.len());
+    scoreboard.insert(position, format!("{} {:.2}", username, score));
+    scoreboard.truncate(scoreboard_size);
+
+    lines.iter().
+        filter(|line| split_score_line(line).0 != pack).
+        cloned().
+        chain(scoreboard.iter().map(|entry| format!("{}\t{}", pack, entry))).
+        collect()
+}
+
+// returns the scoreboard of the pack
+pub fn save_score(ctx: &Context, pack: &str, username: String, score: f32, scoreboard_size: usize) -> Vec<String> {
+    let lines = if filesystem::exists(ctx, SCORES_PATH) { read_file_by_lines(ctx, SCORES_PATH) } else { Vec::new() };
+    let lines = insert_score(&lines, pack, &username, score, scoreboard_size);
 
-    result
-}
\ No newline at end of file
+    if let Ok(mut file) = filesystem::create(ctx, SCORES_PATH) {
+        let _ = file.write(lines.join("\n").as_bytes());
+    }
+
+    pack_scores(&lines, pack)
+}
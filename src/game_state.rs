@@ -0,0 +1,96 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::entities::Word;
+use crate::duplicates::DuplicatePolicy;
+use crate::scoring::{ Combo, Economy, KillCause, Reward };
+
+// top and height are fractions of the playing field height
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FogBand {
+    pub top: f32,
+    pub height: f32
+}
+
+#[derive(Debug)]
+pub struct GameState {
+    // seeded for runs which have to be the same for everyone
+    pub rng: StdRng,
+    pub cash: f32,
+    pub score: f32,
+    pub remaining_lifes: u32,
+    pub combo: Combo,
+    pub economy: Economy,
+    pub words: Vec<Word>,
+    pub duplicate_policy: DuplicatePolicy,
+    pub game_speed_up: f32,
+    pub time_frozen: bool,
+    pub slow_motion: bool,
+    pub shield_active: bool,
+    pub shake_screen: bool,
+    pub fog_bands: Vec<FogBand>,
+    // multipliers of the world events, 1.0 while none is running
+    pub spawn_rate: f32,
+    pub cash_multiplier: f32,
+    // only the typed prefix of the words is visible
+    pub blackout: bool
+}
+
+impl GameState {
+    pub const INITIAL_LIFES: u32 = 5;
+    pub const SLOW_MOTION_SCALE: f32 = 0.5;
+
+    pub fn new() -> Self {
+        GameState {
+            rng: StdRng::from_entropy(),
+            cash: 0.0,
+            score: 0.0,
+            remaining_lifes: GameState::INITIAL_LIFES,
+            combo: Combo::default(),
+            economy: Economy::default(),
+            words: Vec::new(),
+            duplicate_policy: DuplicatePolicy::default(),
+            game_speed_up: 0.0,
+            time_frozen: false,
+            slow_motion: false,
+            shield_active: false,
+            shake_screen: false,
+            fog_bands: Vec::new(),
+            spawn_rate: 1.0,
+            cash_multiplier: 1.0,
+            blackout: false
+        }
+    }
+
+    // removes the word and pays for it according to the economy
+    pub fn kill_word(&mut self, index: usize, cause: KillCause) -> Reward {
+        self.words[index].is_typed = true;
+
+        let mut reward = self.economy.reward(&self.words[index], &self.combo, cause);
+        reward.cash *= self.cash_multiplier;
+
+        self.score += reward.score;
+        self.cash += reward.cash;
+
+        reward
+    }
+
+    // multiplier applied to the elapsed time when moving the words
+    pub fn word_time_scale(&self) -> f32 {
+        if self.time_frozen {
+            return 0.0;
+        }
+
+        if self.slow_motion {
+            return GameState::SLOW_MOTION_SCALE;
+        }
+
+        1.0
+    }
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
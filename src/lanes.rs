@@ -0,0 +1,61 @@
+use ggez::graphics::Rect;
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+// unlike `Rect::overlaps`, touching edges don't count
+fn overlaps(first: &Rect, second: &Rect) -> bool {
+    first.left() < second.right() && first.right() > second.left() &&
+    first.top() < second.bottom() && first.bottom() > second.top()
+}
+
+// horizontal strips of the playing field, one word high each
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lanes {
+    top: f32,
+    lane_height: f32,
+    count: usize
+}
+
+impl Lanes {
+    pub fn new(top: f32, bottom: f32, lane_height: f32) -> Self {
+        let count = if lane_height > 0.0 && bottom > top { ((bottom - top) / lane_height) as usize } else { 0 };
+
+        Lanes {
+            top,
+            lane_height,
+            count: count.max(1)
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn lane_height(&self) -> f32 {
+        self.lane_height
+    }
+
+    // top of the lane where the word sprite should be placed
+    pub fn lane_top(&self, index: usize) -> f32 {
+        self.top + index as f32 * self.lane_height
+    }
+
+    // the part of the lane inside the given columns
+    pub fn lane_rect(&self, index: usize, left: f32, right: f32) -> Rect {
+        Rect::new(left, self.lane_top(index), right - left, self.lane_height)
+    }
+
+    // a lane is occupied when a word overlaps it inside the spawn zone
+    pub fn occupied(&self, spawn_zone: (f32, f32), word_rects: &[Rect]) -> Vec<bool> {
+        (0 .. self.count).map(|index| {
+            let lane = self.lane_rect(index, spawn_zone.0, spawn_zone.1);
+            word_rects.iter().any(|rect| overlaps(rect, &lane))
+        }).collect()
+    }
+
+    // random free lane, `None` when every lane is taken
+    pub fn pick_free<R: Rng>(&self, occupied: &[bool], rng: &mut R) -> Option<usize> {
+        let free = occupied.iter().take(self.count).enumerate().filter(|(_, &taken)| !taken).map(|(index, _)| index).collect::<Vec<usize>>();
+        free.choose(rng).copied()
+    }
+}
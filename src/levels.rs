@@ -0,0 +1,313 @@
+use rand::Rng;
+
+use std::collections::BTreeMap;
+
+use crate::entities::WordKind;
+use crate::modifiers::ModifierRegistry;
+
+// a level is a `.level` file in `/levels`, the campaign plays them in the order of their file names
+pub const LEVELS_DIR: &str = "/levels";
+pub const LEVEL_EXTENSION: &str = "level";
+pub const MAX_STARS: usize = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wave {
+    // words spawned before the wave ends
+    pub count: usize,
+    // spawned in this order, words of the pack when empty
+    pub words: Vec<String>,
+    // picked at random for every word, only normal words when empty
+    pub kinds: Vec<WordKind>,
+    // seconds between two words
+    pub cadence: (f32, f32),
+    pub speed: (f32, f32),
+    // names of the modifiers running during the wave
+    pub modifiers: Vec<String>,
+    // seconds without words after the wave
+    pub pause: f32
+}
+
+impl Default for Wave {
+    fn default() -> Self {
+        Wave {
+            count: 10,
+            words: Vec::new(),
+            kinds: Vec::new(),
+            cadence: (2.5, 3.0),
+            speed: (100.0, 200.0),
+            modifiers: Vec::new(),
+            pause: 3.0
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    // the file name without its extension, which is what gets saved with the stars
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    // the pack the words come from
+    pub pack: String,
+    // scores for one, two and three stars
+    pub stars: [f32; MAX_STARS],
+    pub waves: Vec<Wave>
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value.split(',').map(|item| item.trim().to_string()).filter(|item| !item.is_empty()).collect()
+}
+
+fn parse_number(value: &str) -> Result<f32, String> {
+    value.trim().parse::<f32>().ok().filter(|number| number.is_finite() && *number >= 0.0).ok_or(format!("'{}' isn't a number", value.trim()))
+}
+
+// `min .. max` or a single number
+fn parse_range(value: &str) -> Result<(f32, f32), String> {
+    let (min, max) = match value.split_once("..") {
+        Some((min, max)) => (parse_number(min)?, parse_number(max)?),
+        None => (parse_number(value)?, parse_number(value)?)
+    };
+
+    if min > max {
+        return Err(format!("'{}' ends before it starts", value));
+    }
+
+    Ok((min, max))
+}
+
+fn parse_kind(name: &str) -> Result<WordKind, String> {
+    match name {
+        "normal" => Ok(WordKind::Normal),
+        "armored" => Ok(WordKind::Armored { hits_left: WordKind::ARMOR_HITS }),
+        "splitting" => Ok(WordKind::Splitting),
+        "golden" => Ok(WordKind::Golden),
+        _ => Err(format!("unknown word kind '{}'", name))
+    }
+}
+
+impl Level {
+    // `key: value` lines like a pack manifest, every `wave` line starts a new wave and the keys after it belong to it
+    pub fn parse(id: &str, text: &str) -> Result<Level, String> {
+        let mut level = Level {
+            id: id.to_string(),
+            name: String::new(),
+            description: String::new(),
+            pack: String::new(),
+            stars: [0.0; MAX_STARS],
+            waves: Vec::new()
+        };
+        // whether the wave got its count from a `count` line, otherwise a word list sets it
+        let mut counted = false;
+        let modifier_names = ModifierRegistry::with_defaults().names();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            let error = |message: String| format!("line {}: {}", number + 1, message);
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line == "wave" {
+                level.waves.push(Wave::default());
+                counted = false;
+                continue;
+            }
+
+            let (key, value) = line.split_once(':').ok_or_else(|| error(String::from("expected 'key: value' or 'wave'")))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            match (key, level.waves.last_mut()) {
+                ("name", None) => level.name = value.to_string(),
+                ("description", None) => level.description = value.to_string(),
+                ("pack", None) => level.pack = value.to_string(),
+                ("stars", None) => {
+                    let stars = split_list(value).iter().map(|star| parse_number(star)).collect::<Result<Vec<f32>, String>>().map_err(error)?;
+                    level.stars = stars.try_into().map_err(|_| error(format!("expected {} scores", MAX_STARS)))?;
+                },
+                ("count", Some(wave)) => {
+                    wave.count = value.parse().map_err(|_| error(format!("'{}' isn't a count", value)))?;
+                    counted = true;
+                },
+                ("words", Some(wave)) => {
+                    wave.words = split_list(value);
+                    if !counted {
+                        wave.count = wave.words.len();
+                    }
+                },
+                ("kinds", Some(wave)) => wave.kinds = split_list(value).iter().map(|kind| parse_kind(kind)).collect::<Result<Vec<WordKind>, String>>().map_err(error)?,
+                ("cadence", Some(wave)) => wave.cadence = parse_range(value).map_err(error)?,
+                ("speed", Some(wave)) => wave.speed = parse_range(value).map_err(error)?,
+                ("modifiers", Some(wave)) => {
+                    wave.modifiers = split_list(value);
+
+                    if let Some(unknown) = wave.modifiers.iter().find(|name| !modifier_names.contains(name)) {
+                        return Err(error(format!("unknown modifier '{}'", unknown)));
+                    }
+                },
+                ("pause", Some(wave)) => wave.pause = parse_number(value).map_err(error)?,
+                (_, None) => return Err(error(format!("'{}' isn't a level key", key))),
+                (_, Some(_)) => return Err(error(format!("'{}' isn't a wave key", key)))
+            }
+        }
+
+        if level.name.is_empty() {
+            return Err(String::from("the level has no name"));
+        }
+
+        if level.waves.is_empty() {
+            return Err(String::from("the level has no waves"));
+        }
+
+        if level.stars.windows(2).any(|pair| pair[0] > pair[1]) {
+            return Err(String::from("the scores for the stars have to grow"));
+        }
+
+        Ok(level)
+    }
+
+    // stars for a score, only cleared levels get any
+    pub fn stars_for(&self, score: f32) -> usize {
+        self.stars.iter().filter(|stars| score >= **stars).count()
+    }
+}
+
+// what the script spawns next
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptedWord {
+    // a word of the pack when `None`
+    pub word: Option<String>,
+    pub kind: WordKind,
+    pub speed: f32
+}
+
+// plays the waves of a level one after the other
+#[derive(Debug, Clone)]
+pub struct LevelRun {
+    level: Level,
+    wave: usize,
+    spawned: usize,
+    time_until_next: f32,
+    // set when the last wave started, so the modifiers switch only once per wave
+    wave_started: bool
+}
+
+impl LevelRun {
+    pub fn new(level: Level) -> Self {
+        LevelRun {
+            level,
+            wave: 0,
+            spawned: 0,
+            time_until_next: 0.0,
+            wave_started: false
+        }
+    }
+
+    pub fn level(&self) -> &Level {
+        &self.level
+    }
+
+    // counted from 1, like on the screen
+    pub fn wave_number(&self) -> usize {
+        (self.wave + 1).min(self.level.waves.len())
+    }
+
+    pub fn current_wave(&self) -> Option<&Wave> {
+        self.level.waves.get(self.wave)
+    }
+
+    // every word of every wave has been spawned
+    pub fn is_finished(&self) -> bool {
+        self.wave >= self.level.waves.len()
+    }
+
+    // returns the wave which just started, its modifiers replace those of the wave before
+    pub fn take_started_wave(&mut self) -> Option<&Wave> {
+        if self.wave_started || self.is_finished() {
+            return None;
+        }
+
+        self.wave_started = true;
+        self.current_wave()
+    }
+
+    pub fn update<R: Rng>(&mut self, seconds: f32, rng: &mut R) -> Option<ScriptedWord> {
+        self.time_until_next -= seconds;
+
+        let wave = self.level.waves.get(self.wave)?;
+        if self.time_until_next > 0.0 || !self.wave_started {
+            return None;
+        }
+
+        if self.spawned >= wave.count {
+            // the pause is over
+            self.wave += 1;
+            self.spawned = 0;
+            self.wave_started = false;
+            return None;
+        }
+
+        let word = (!wave.words.is_empty()).then(|| wave.words[self.spawned % wave.words.len()].clone());
+        let kind = if wave.kinds.is_empty() { WordKind::Normal } else { wave.kinds[rng.gen_range(0 .. wave.kinds.len())] };
+        let speed = rng.gen_range(wave.speed.0 ..= wave.speed.1);
+
+        self.spawned += 1;
+        self.time_until_next = if self.spawned < wave.count { rng.gen_range(wave.cadence.0 ..= wave.cadence.1) } else { wave.pause };
+
+        Some(ScriptedWord { word, kind, speed })
+    }
+}
+
+// the best stars of every level, saved between games
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CampaignProgress {
+    pub stars: BTreeMap<String, usize>
+}
+
+impl CampaignProgress {
+    pub fn stars(&self, level: &str) -> usize {
+        self.stars.get(level).copied().unwrap_or(0)
+    }
+
+    // a worse result doesn't take stars away, returns whether the level got more
+    pub fn record(&mut self, level: &str, stars: usize) -> bool {
+        if stars <= self.stars(level) {
+            return false;
+        }
+
+        self.stars.insert(level.to_string(), stars);
+        true
+    }
+
+    // the first level is always open, the others once the level before has a star
+    pub fn is_unlocked(&self, levels: &[Level], index: usize) -> bool {
+        index == 0 || levels.get(index - 1).is_some_and(|level| self.stars(&level.id) > 0)
+    }
+
+    pub fn total(&self) -> usize {
+        self.stars.values().sum()
+    }
+
+    // `level<TAB>stars` lines
+    pub fn serialize(&self) -> String {
+        self.stars.iter().map(|(level, stars)| format!("{}\t{}\n", level, stars)).collect()
+    }
+
+    // broken lines are skipped
+    pub fn parse(text: &str) -> Self {
+        let stars = text.lines().
+            filter_map(|line| {
+                let (level, stars) = line.split_once('\t')?;
+                Some((level.to_string(), stars.trim().parse::<usize>().ok()?.min(MAX_STARS)))
+            }).
+            collect();
+
+        CampaignProgress { stars }
+    }
+}
+
+// `***` with the missing stars as dots, the font has no star glyph
+pub fn star_label(stars: usize) -> String {
+    (0 .. MAX_STARS).map(|star| if star < stars { '*' } else { '.' }).collect()
+}
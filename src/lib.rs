@@ -2,4 +2,28 @@ pub mod debug;
 pub mod assets;
 pub mod entities;
 pub mod draw_helper;
-pub mod filesystem_helper;
\ No newline at end of file
+pub mod filesystem_helper;
+pub mod game_state;
+pub mod power_ups;
+pub mod modifiers;
+pub mod movement;
+pub mod scoring;
+pub mod lanes;
+pub mod duplicates;
+pub mod word_sources;
+pub mod cli;
+pub mod word_packs;
+pub mod difficulty;
+pub mod content_filter;
+pub mod settings;
+pub mod dictionary;
+pub mod training;
+pub mod modes;
+pub mod review;
+pub mod vocabulary;
+pub mod arithmetic;
+pub mod word_chain;
+pub mod daily;
+pub mod challenge;
+pub mod levels;
+pub mod events;
\ No newline at end of file
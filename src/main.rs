@@ -1,23 +1,59 @@
 use ggez::audio::SoundSource;
 use ggez::conf::{ Conf, WindowMode };
 use ggez::{ event, timer, filesystem, graphics };
-use ggez::{ Context, ContextBuilder, GameResult };
+use ggez::{ Context, ContextBuilder, GameError, GameResult };
 use ggez::input::keyboard::is_key_pressed;
 use ggez::mint::Point2;
-use rand::{ Rng, seq };
-use rand::rngs::ThreadRng;
+use rand::{ Rng, SeedableRng, seq };
+use rand::rngs::{ StdRng, ThreadRng };
 
 use type_racer::assets::{ Assets, TextSprite, Sprite };
-use type_racer::entities::Word;
+use type_racer::entities::{ Word, WordKind, Popup, Visibility };
+use type_racer::game_state::GameState;
+use type_racer::power_ups::PowerUpRegistry;
+use type_racer::modifiers::{ ModifierRegistry, SpeedUp };
+use type_racer::events::EventScheduler;
+use type_racer::movement::{ self, SpawnSide, Linear };
+use type_racer::scoring::KillCause;
+use type_racer::lanes::Lanes;
+use type_racer::duplicates::DuplicatePolicy;
+use type_racer::word_sources::{ WordSource, DictionarySource, MarkovGenerator, PassageSource };
+use type_racer::cli::{ self, Command, Options, SourceKind };
+use type_racer::word_packs::{ self, WordPack };
+use type_racer::content_filter::{ ContentFilter, Filtered };
+use type_racer::settings::Settings;
+use type_racer::training::{ self, TypingStats, Improvement, AdaptiveSource };
+use type_racer::modes::GameMode;
+use type_racer::review::{ self, MistakeLog, ReviewSource };
+use type_racer::vocabulary::{ self, VocabularySource };
+use type_racer::arithmetic::ArithmeticSource;
+use type_racer::word_chain::ChainSource;
+use type_racer::daily::{ self, Date, DailyChallenge, DailyLog, DailyResult };
+use type_racer::challenge::{ self, Ruleset };
+use type_racer::levels::{ self, Level, LevelRun, ScriptedWord, CampaignProgress };
 use type_racer::debug;
 use type_racer::draw_helper;
 use type_racer::filesystem_helper;
+use type_racer::dictionary;
 
 use std::str;
 use std::env;
 use std::path;
+use std::process;
+use std::fs;
+use std::collections::HashSet;
+use std::time::Instant;
 
 fn main() {
+    let options = match cli::parse_command(env::args().skip(1)) {
+        Ok(Command::Play(options)) => options,
+        Ok(Command::Validate { path, output }) => process::exit(validate_dictionary(&path, output.as_deref())),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    };
+
     let conf = Conf::new()
     .window_mode(WindowMode {
         width: 1200.0,
@@ -39,267 +75,1120 @@ fn main() {
         filesystem::mount(&mut ctx, &path, true);
     }
 
-    let state = MainState::new(&mut ctx, &conf).unwrap();
+    let state = match MainState::new(&mut ctx, &conf, &options) {
+        Ok(state) => state,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    };
 
     event::run(ctx, event_loop, state);
 }
 
+// prints the report and returns the exit code
+fn validate_dictionary(path: &str, output: Option<&str>) -> i32 {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) => {
+            eprintln!("Error with opening {}: {}", path, error);
+            return 2;
+        }
+    };
+
+    let report = dictionary::validate(&text);
+    println!("{}:\n{}", path, report);
+
+    if let Some(output) = output {
+        if let Err(error) = fs::write(output, dictionary::normalize(&text)) {
+            eprintln!("Error with writing {}: {}", output, error);
+            return 2;
+        }
+
+        println!("normalized dictionary written to {}", output);
+    }
+
+    if report.is_clean() { 0 } else { 1 }
+}
+
 struct MainState {
-    rng: ThreadRng,
+    game: GameState,
     assets: Assets,
     info_panel: TextSprite,
     sound_volume: f32,
     show_info: bool,
+    in_menu: bool,
     game_over: bool,
     saved_score: bool,
     current_input: String,
-    cash: f32,
-    score: f32,
-    remaining_lifes: u32,
-    words: Vec<Word>,
     time_until_next_word: f32,
-    game_speed_up: f32,
-    time_until_shake: f32,
-    shake_screen: bool,
-    shake_time: f32,
+    time_until_boss: f32,
     screen_width: f32,
     screen_height: f32,
+    options: Options,
+    settings: Settings,
+    packs: Vec<WordPack>,
+    selected_pack: usize,
+    mode: GameMode,
+    // every game before this one
+    typing_stats: TypingStats,
+    session_stats: TypingStats,
+    last_key_time: Option<Instant>,
+    improvements: Vec<Improvement>,
+    mistakes: MistakeLog,
+    // set while a daily challenge is played
+    daily: Option<DailyChallenge>,
+    daily_log: DailyLog,
+    // whether this daily run is the ranked one of the day
+    daily_ranked: bool,
+    // the saved profile the daily attempts are ranked for
+    daily_profile: String,
+    // the rules of the running game, shared as a challenge code
+    ruleset: Option<Ruleset>,
+    levels: Vec<Level>,
+    selected_level: usize,
+    campaign: CampaignProgress,
+    // set while a campaign level is played
+    level_run: Option<LevelRun>,
+    // stars of the finished level, `None` when it was lost
+    level_stars: Option<usize>,
+    // result of the last menu action
+    menu_message: Option<String>,
     words_pool: Vec<String>,
+    word_source: Box<dyn WordSource>,
     scoreboard: Vec<String>,
-    power_up_panels: Vec<TextSprite>
+    lanes: Lanes,
+    power_ups: PowerUpRegistry,
+    modifiers: ModifierRegistry,
+    events: EventScheduler,
+    popups: Vec<Popup>,
+    // hidden words shown again as they escape
+    reveals: Vec<Popup>,
+    // not the seeded game rng, how often the screen shakes depends on the frame rate
+    shake_rng: ThreadRng
 }
 
 impl MainState {
-    const BUY_LIFE_TAX: f32 = 300.0;
-    const REMOVE_WORDS_TAX: f32 = 350.0;
-    const SLOW_WORD_SPAWN_TAX: f32 = 1000.0;
-    const REMOVE_WORDS_COUNT: usize = 2;
     const INITAL_SOUND_VOLUME: f32 = 0.05;
     const SOUND_VOLUME_STEP: f32 = 0.005;
     const SCOREBOARD_SIZE: usize = 10;
     const TOP_PANEL_TEXT_SIZE: f32 = 34.0;
     const BOT_PANEL_TEXT_SIZE: f32 = 40.0;
     const CENTER_PANEL_TEXT_SIZE: f32 = 40.0;
-    const SHAKE_DURATION: f32 = 1.0;
+    const INFO_PANEL_TEXT_SIZE: f32 = 28.0;
     const SHAKE_MAGNITUDE: f32 = 3.0;
-
-    fn new(ctx: &mut Context, conf: &Conf) -> GameResult<MainState> {
+    const WORD_TEXT_SIZE: f32 = 32.0;
+    const BOSS_TEXT_SIZE: f32 = 40.0;
+    const BOSS_INTERVAL: f32 = 60.0;
+    const BOSS_SENTENCE_WORDS: usize = 4;
+    const BOSS_LIVES: u32 = 3;
+    const BOSS_SPEED: f32 = 60.0;
+    const SPLIT_OFFSET: f32 = 20.0;
+    const LANE_PADDING: f32 = 4.0;
+    const LANE_RETRY_TIME: f32 = 0.25;
+    const SPAWN_ZONE_WIDTH: f32 = 300.0;
+    const WORD_PICK_ATTEMPTS: usize = 10;
+    // game speed-up from which the hardest words of the dictionary are picked
+    const HARDEST_WORDS_SPEED_UP: f32 = 2.0;
+    // game speed-up of each difficulty level, the highest one starts with the hardest words
+    const DIFFICULTY_STEP: f32 = MainState::HARDEST_WORDS_SPEED_UP / challenge::MAX_DIFFICULTY as f32;
+    const POPUP_TEXT_SIZE: f32 = 26.0;
+    const POPUP_COLOR: graphics::Color = graphics::Color::new(0.4, 1.0, 0.4, 1.0);
+    const REVEAL_COLOR: graphics::Color = graphics::Color::new(1.0, 0.4, 0.4, 1.0);
+    // seconds a word of the memory mode is visible after spawning
+    const MEMORY_FLASH_TIME: f32 = 1.5;
+    const COOLDOWN_BAR_HEIGHT: f32 = 4.0;
+    const FOG_COLOR: graphics::Color = graphics::Color::new(0.3, 0.3, 0.3, 1.0);
+    const BLACKOUT_COLOR: graphics::Color = graphics::Color::new(0.0, 0.0, 0.0, 0.92);
+    const EVENT_COLOR: graphics::Color = graphics::Color::new(1.0, 0.55, 0.1, 1.0);
+    const WARNING_BACKGROUND_COLOR: graphics::Color = graphics::Color::new(0.15, 0.15, 0.15, 0.9);
+    const MENU_MARGIN: f32 = 40.0;
+    const CALENDAR_CELL_WIDTH: f32 = 80.0;
+    const CALENDAR_CELL_HEIGHT: f32 = 70.0;
+    // a longer pause is a break, not a slow key
+    const MAX_KEY_LATENCY: f32 = 2.0;
+    const SHOWN_IMPROVEMENTS: usize = 3;
+    const DEFAULT_PROFILE: &'static str = "Player";
+    const POWER_UP_TIMER_COLOR: graphics::Color = graphics::Color::new(1.0, 0.84, 0.0, 1.0);
+
+    fn new(ctx: &mut Context, conf: &Conf, options: &Options) -> GameResult<MainState> {
         let mut assets = Assets::new(ctx)?;
         assets.background_music.set_volume(MainState::INITAL_SOUND_VOLUME);
         let _ = assets.background_music.play(ctx);
-        let words = filesystem_helper::read_file_by_lines(ctx, "/words.dict");
-
-        let info_panel_label = format!(
-"(+) to volume up
-(-) to volume down
-
-Buffs become visible when you have the required cash:
-(1) for extra life  ({:.2}$)
-(2) for words removal  ({:.2}$)
-(3) for slow words spawn  ({:.2}$)
 
-(Esc) to quit",
-                           MainState::BUY_LIFE_TAX,
-                           MainState::REMOVE_WORDS_TAX,
-                           MainState::SLOW_WORD_SPAWN_TAX);
-        let info_panel = TextSprite::new(&info_panel_label, ctx, MainState::CENTER_PANEL_TEXT_SIZE)?;
+        let packs = filesystem_helper::discover_packs(ctx);
+        if packs.is_empty() {
+            panic!("No word packs in {}!", word_packs::PACKS_DIR);
+        }
 
-        let slow_word_spawn_label = format!("(3) Slow spawn ({:.2}$)", MainState::SLOW_WORD_SPAWN_TAX);
-        let slow_word_spawn_panel = TextSprite::new(&slow_word_spawn_label, ctx, MainState::TOP_PANEL_TEXT_SIZE)?;
+        // the pack from the command line skips the menu, otherwise the last used one is preselected
+        let find_pack = |id: &str| packs.iter().position(|pack| pack.id == id);
+        let pack = options.challenge.as_ref().map(|ruleset| &ruleset.pack).or(options.pack.as_ref());
+        let selected_pack = match pack {
+            Some(id) => find_pack(id).ok_or_else(|| GameError::ResourceLoadError(format!("Unknown word pack {}!", id)))?,
+            None => filesystem_helper::load_last_pack(ctx).
+                and_then(|id| find_pack(&id)).
+                or_else(|| find_pack(word_packs::DEFAULT_PACK)).
+                unwrap_or(0)
+        };
 
-        let remove_words_label = format!("(2) Remove {} words ({:.2}$)",MainState::REMOVE_WORDS_COUNT , MainState::REMOVE_WORDS_TAX);
-        let remove_words_panel = TextSprite::new(&remove_words_label, ctx, MainState::TOP_PANEL_TEXT_SIZE)?;
+        if let Some(profile) = &options.profile {
+            filesystem_helper::save_profile(ctx, profile);
+        }
 
-        let extra_life_label = format!("(1) extra life ({:.2}$)", MainState::BUY_LIFE_TAX);
-        let extra_life_panel = TextSprite::new(&extra_life_label, ctx, MainState::TOP_PANEL_TEXT_SIZE)?;
+        let game = GameState::new();
 
-        let mut power_up_panels = Vec::new();
-        power_up_panels.push(slow_word_spawn_panel);
-        power_up_panels.push(remove_words_panel);
-        power_up_panels.push(extra_life_panel);
+        let lane_height = TextSprite::new("Wy", ctx, MainState::WORD_TEXT_SIZE)?.height(ctx) + MainState::LANE_PADDING;
+        let (top_height, bot_height) = MainState::field_bounds_for(conf.window_mode.height);
+        let lanes = Lanes::new(top_height, bot_height, lane_height);
 
-        let start_state = MainState {
-            rng: rand::thread_rng(),
-            assets: assets,
-            info_panel,
+        let mut start_state = MainState {
+            game,
+            assets,
+            // the pack dependent parts are filled in when the game starts
+            info_panel: TextSprite::new("", ctx, MainState::INFO_PANEL_TEXT_SIZE)?,
             sound_volume: MainState::INITAL_SOUND_VOLUME,
             show_info: false,
+            in_menu: true,
             game_over: false,
             saved_score: false,
             current_input: String::new(),
-            cash: 0.0,
-            score: 0.0,
-            remaining_lifes: 5,
-            words: Vec::new(),
             time_until_next_word: 3.0,
-            game_speed_up: 0.0,
-            time_until_shake: 10.0,
-            shake_screen: false,
-            shake_time: MainState::SHAKE_DURATION,
+            time_until_boss: MainState::BOSS_INTERVAL,
             screen_width: conf.window_mode.width,
             screen_height: conf.window_mode.height,
-            words_pool: words,
+            options: options.clone(),
+            settings: filesystem_helper::load_settings(ctx),
+            packs,
+            selected_pack,
+            mode: options.challenge.as_ref().map(|ruleset| ruleset.mode).unwrap_or(options.mode),
+            typing_stats: filesystem_helper::load_typing_stats(ctx),
+            session_stats: TypingStats::default(),
+            last_key_time: None,
+            improvements: Vec::new(),
+            mistakes: filesystem_helper::load_mistakes(ctx),
+            daily: None,
+            daily_log: filesystem_helper::load_daily_log(ctx),
+            daily_ranked: false,
+            daily_profile: options.profile.clone().or_else(|| filesystem_helper::load_profile(ctx)).unwrap_or_else(|| String::from(MainState::DEFAULT_PROFILE)),
+            ruleset: None,
+            levels: filesystem_helper::discover_levels(ctx),
+            selected_level: 0,
+            campaign: filesystem_helper::load_campaign(ctx),
+            level_run: None,
+            level_stars: None,
+            menu_message: None,
+            words_pool: Vec::new(),
+            word_source: Box::new(DictionarySource::new(Vec::new(), None)),
             scoreboard: Vec::new(),
-            power_up_panels
+            lanes,
+            power_ups: PowerUpRegistry::with_defaults(),
+            modifiers: ModifierRegistry::with_defaults(),
+            events: EventScheduler::new(),
+            popups: Vec::new(),
+            reveals: Vec::new(),
+            shake_rng: rand::thread_rng()
         };
 
+        if pack.is_some() {
+            start_state.start_game(ctx)?;
+        }
+
         Ok(start_state)
     }
+
+    // loads the selected pack and leaves the menu
+    fn start_game(&mut self, ctx: &mut Context) -> GameResult<()> {
+        // a challenge code replays its own rules, even those of a daily challenge
+        let is_daily = self.mode == GameMode::Daily && self.options.challenge.is_none();
+        self.daily = is_daily.then(|| self.daily_challenge(Date::today()));
+
+        if self.mode == GameMode::Campaign {
+            let level = match self.levels.get(self.selected_level) {
+                Some(level) => level,
+                None => {
+                    self.menu_message = Some(format!("No levels in {}", levels::LEVELS_DIR));
+                    return Ok(());
+                }
+            };
+
+            if !self.campaign.is_unlocked(&self.levels, self.selected_level) {
+                self.menu_message = Some(String::from("Get a star in the level before to unlock this one"));
+                return Ok(());
+            }
+
+            match self.packs.iter().position(|pack| pack.id == level.pack) {
+                Some(pack) => self.selected_pack = pack,
+                None => {
+                    self.menu_message = Some(format!("{} needs the word pack {}", level.name, level.pack));
+                    return Ok(());
+                }
+            }
+
+            // the waves bring their own modifiers
+            self.level_run = Some(LevelRun::new(level.clone()));
+        }
+
+        let ruleset = match (&self.options.challenge, &self.daily) {
+            (Some(ruleset), _) => ruleset.clone(),
+            (None, Some(challenge)) => {
+                let mut modifiers = challenge.modifiers.clone();
+                modifiers.push(String::from(SpeedUp::NAME));
+
+                Ruleset {
+                    seed: challenge.seed,
+                    mode: self.mode,
+                    difficulty: challenge.difficulty,
+                    source: SourceKind::Dictionary,
+                    order: MarkovGenerator::DEFAULT_ORDER as u8,
+                    min_length: MarkovGenerator::DEFAULT_MIN_LENGTH as u8,
+                    max_length: MarkovGenerator::DEFAULT_MAX_LENGTH as u8,
+                    content_filter: true,
+                    pack: challenge.pack.clone(),
+                    modifiers
+                }
+            },
+            (None, None) => Ruleset {
+                seed: self.options.seed.unwrap_or_else(rand::random),
+                mode: self.mode,
+                difficulty: self.options.difficulty,
+                source: self.options.source,
+                order: byte(self.options.order),
+                min_length: byte(self.options.min_length),
+                max_length: byte(self.options.max_length),
+                content_filter: self.settings.content_filter,
+                pack: self.packs[self.selected_pack].id.clone(),
+                modifiers: if self.level_run.is_some() { Vec::new() } else { ModifierRegistry::with_defaults().names() }
+            }
+        };
+
+        // another pack would make a different run than everyone else's
+        self.selected_pack = match self.packs.iter().position(|pack| pack.id == ruleset.pack) {
+            Some(index) => index,
+            None => {
+                self.menu_message = Some(format!("The pack '{}' of this run is missing", ruleset.pack));
+                self.daily = None;
+                return Ok(());
+            }
+        };
+        self.modifiers = ModifierRegistry::with_defaults();
+        self.modifiers.keep(&ruleset.modifiers);
+        self.game.rng = StdRng::seed_from_u64(ruleset.seed);
+        if self.daily.is_some() {
+            self.game.duplicate_policy = DuplicatePolicy::default();
+        }
+        self.game.game_speed_up = ruleset.difficulty as f32 * MainState::DIFFICULTY_STEP;
+
+        let seed = Some(ruleset.seed);
+        let content_filter = ruleset.content_filter;
+        let (source, order, min_length, max_length) = (ruleset.source, ruleset.order as usize, ruleset.min_length as usize, ruleset.max_length as usize);
+        self.ruleset = Some(ruleset);
+        self.events = EventScheduler::for_mode(self.mode, &filesystem_helper::read_event_config(ctx));
+
+        let pack = &self.packs[self.selected_pack];
+        let lines = filesystem_helper::read_pack_words(ctx, pack);
+        let words = vocabulary::answers(&lines);
+        // shared runs ignore the player's own blocklist and tags, so everyone gets the same words
+        let shared = self.daily.is_some() || self.options.challenge.is_some();
+        let filter = content_filter.then(|| {
+            let filter = match shared {
+                true => ContentFilter::new(&filesystem_helper::read_shipped_blocklist(ctx), &Settings::default().excluded_tags),
+                false => ContentFilter::new(&filesystem_helper::read_blocklist(ctx), &self.settings.excluded_tags)
+            };
+            (filter, filesystem_helper::read_word_tags(ctx, pack))
+        });
+        let Filtered { words, removed } = match &filter {
+            Some((filter, word_tags)) => filter.apply(words, word_tags),
+            None => Filtered { words, removed: 0 }
+        };
+        let options = &self.options;
+
+        // pairs are filtered by their answers and by their prompts, which can be a few words
+        let allowed = words.iter().collect::<HashSet<&String>>();
+        let pairs = vocabulary::pairs(&lines).into_iter().
+            filter(|pair| allowed.contains(&pair.answer)).
+            filter(|pair| match &filter {
+                Some((filter, word_tags)) => filter.allows(&pair.prompt, word_tags) && pair.prompt.split_whitespace().all(|word| filter.allows(word, word_tags)),
+                None => true
+            }).
+            collect::<Vec<_>>();
+
+        if self.mode == GameMode::Vocabulary && pairs.is_empty() {
+            self.menu_message = Some(format!("{} has no prompt/answer pairs", pack.manifest.name));
+            return Ok(());
+        }
+
+        self.word_source = match (self.mode, source) {
+            (GameMode::Vocabulary, _) => Box::new(VocabularySource::new(pairs, seed)),
+            (GameMode::Arithmetic, _) => Box::new(ArithmeticSource::new(seed)),
+            (GameMode::WordChain, _) => Box::new(ChainSource::new(words.clone(), seed)),
+            (GameMode::Adaptive, _) => Box::new(AdaptiveSource::new(words.clone(), &self.typing_stats, seed)),
+            (GameMode::Review, _) => Box::new(ReviewSource::new(self.mistakes.due_words(review::now()), words.clone(), seed)),
+            (GameMode::Daily, _) | (_, SourceKind::Dictionary) => Box::new(DictionarySource::new(words.clone(), seed)),
+            (_, SourceKind::Generated) => Box::new(MarkovGenerator::new(&words, order, min_length, max_length, seed)),
+            (_, SourceKind::Passage) => Box::new(PassageSource::new(&filesystem_helper::read_file_by_lines(ctx, &options.passage).join("\n")))
+        };
+        self.words_pool = words;
+
+        let filter_label = if content_filter { format!("on, {} words hidden", removed) } else { String::from("off") };
+        let mut info_panel_label = format!(
+"Pack: {}
+Mode: {}
+Words: {}
+Family friendly: {}
+
+(+) to volume up
+(-) to volume down
+(Tab) to switch what happens when several words are the same
+", pack.manifest.name, self.mode.name(), self.word_source.name(), filter_label);
+        if self.mode.has_power_ups() {
+            info_panel_label.push_str("
+Buffs become visible when you have the required cash.
+(Shift + N) buys one for later, (N) uses it.
+Prices grow with every purchase:
+");
+            for power_up in self.power_ups.power_ups() {
+                info_panel_label.push_str(&format!("({}) {}  (from {:.2}$)\n", power_up.key_label(), power_up.description(), power_up.cost()));
+            }
+        }
+        info_panel_label.push_str(&format!("\n{}\n", self.game.economy.describe()));
+        if let Some(challenge) = &self.daily {
+            info_panel_label.push_str(&format!("\nDaily challenge {} with {}\n", challenge.date, challenge.modifiers.join(", ")));
+        }
+        if let Some(run) = &self.level_run {
+            let level = run.level();
+            info_panel_label.push_str(&format!("\nLevel {}: {}\nStars from {}\n", level.name, level.description,
+                                               level.stars.iter().map(|stars| format!("{:.0}", stars)).collect::<Vec<String>>().join(", ")));
+        }
+        info_panel_label.push_str("\n(Esc) to quit");
+        self.info_panel = TextSprite::new(&info_panel_label, ctx, MainState::INFO_PANEL_TEXT_SIZE)?;
+
+        // the attempt counts from its start, quitting doesn't give another one
+        if let Some(challenge) = &self.daily {
+            let attempt = DailyResult { date: challenge.date, profile: self.daily_profile.clone(), score: 0.0 };
+            self.daily_ranked = self.daily_log.record(attempt);
+
+            if self.daily_ranked {
+                filesystem_helper::save_daily_log(ctx, &self.daily_log);
+            }
+        }
+
+        filesystem_helper::save_last_pack(ctx, &pack.id);
+        self.in_menu = false;
+        self.menu_message = None;
+
+        Ok(())
+    }
+
+    // after the game is over, the next level of the campaign is preselected when this one was cleared
+    fn return_to_menu(&mut self) {
+        if self.level_stars.is_some_and(|stars| stars > 0) {
+            self.selected_level = (self.selected_level + 1).min(self.levels.len().saturating_sub(1));
+        }
+
+        self.game = GameState::new();
+        self.modifiers = ModifierRegistry::with_defaults();
+        self.power_ups = PowerUpRegistry::with_defaults();
+        self.events = EventScheduler::new();
+        self.level_run = None;
+        self.level_stars = None;
+        self.daily = None;
+        self.daily_ranked = false;
+        self.ruleset = None;
+        // a challenge code is played once, the menu starts normal games
+        self.options.challenge = None;
+        self.time_until_next_word = 3.0;
+        self.time_until_boss = MainState::BOSS_INTERVAL;
+        self.current_input = String::new();
+        self.last_key_time = None;
+        self.popups.clear();
+        self.reveals.clear();
+        self.improvements.clear();
+        self.scoreboard.clear();
+        self.show_info = false;
+        self.game_over = false;
+        self.saved_score = false;
+        self.in_menu = true;
+    }
+
+    // adds the keys of this game to the saved ones, at most once per game
+    fn finish_session(&mut self, ctx: &Context) {
+        filesystem_helper::save_mistakes(ctx, &self.mistakes);
+
+        if let Some(challenge) = self.daily.as_ref().filter(|_| self.daily_ranked) {
+            self.daily_log.set_score(challenge.date, &self.daily_profile, self.game.score);
+            filesystem_helper::save_daily_log(ctx, &self.daily_log);
+        }
+
+        if self.session_stats.keys.is_empty() {
+            return;
+        }
+
+        self.improvements = training::improvements(&self.typing_stats, &self.session_stats);
+        self.typing_stats.merge(&self.session_stats);
+        self.session_stats = TypingStats::default();
+        filesystem_helper::save_typing_stats(ctx, &self.typing_stats);
+    }
+
+    fn record_key(&mut self, correct: bool) {
+        let mut letters = self.current_input.chars().rev();
+        let letter = match letters.next() {
+            Some(letter) if letter.is_ascii_alphabetic() => letter,
+            _ => return
+        };
+        // pairs and latencies only count inside a word
+        let previous = letters.next().filter(|previous| previous.is_ascii_alphabetic());
+
+        let now = Instant::now();
+        let latency = self.last_key_time.
+            filter(|_| previous.is_some()).
+            map(|last_key_time| now.duration_since(last_key_time).as_secs_f32()).
+            filter(|latency| *latency <= MainState::MAX_KEY_LATENCY);
+        self.last_key_time = Some(now);
+
+        self.session_stats.record(previous, letter, correct, latency);
+    }
+
+    // only shipped packs and the modifiers in their usual order, which are the same for everyone
+    fn daily_challenge(&self, date: Date) -> DailyChallenge {
+        let packs = word_packs::SHIPPED_PACKS.iter().map(|pack| pack.to_string()).collect::<Vec<String>>();
+        let modifiers = ModifierRegistry::with_defaults().names().into_iter().
+            filter(|name| name != SpeedUp::NAME).
+            collect::<Vec<String>>();
+
+        DailyChallenge::new(date, &packs, &modifiers)
+    }
+
+    fn challenge_code(&self) -> Option<String> {
+        // the code doesn't hold the level, so campaign runs have none
+        self.ruleset.as_ref().
+            filter(|_| self.level_run.is_none()).
+            map(|ruleset| challenge::encode(ruleset, &ModifierRegistry::with_defaults().names()))
+    }
+
+    fn pack_name(&self, id: &str) -> String {
+        self.packs.iter().find(|pack| pack.id == id).map(|pack| pack.manifest.name.clone()).unwrap_or_else(|| id.to_string())
+    }
+
+    // the pack, or the mode and the pack for the modes with their own scoreboards, levels have one each
+    fn scoreboard_id(&self) -> String {
+        let pack = match &self.level_run {
+            Some(run) => &run.level().id,
+            None => &self.packs[self.selected_pack].id
+        };
+
+        if self.mode.has_own_scoreboard() { format!("{}/{}", self.mode.id(), pack) } else { pack.clone() }
+    }
+
+    // the words the player was typing before the wrong key
+    // only the word the input was aimed at gets the miss
+    fn record_typo(&mut self, ctx: &mut Context) {
+        if !self.mode.logs_mistakes() {
+            return;
+        }
+
+        let mut typed = self.current_input.clone();
+        typed.pop();
+
+        if typed.is_empty() {
+            return;
+        }
+
+        let screen = graphics::Rect::new(0.0, 0.0, self.screen_width, self.screen_height);
+        let word_rects = self.game.words.iter().map(|word| word.bounding_rect(ctx)).collect::<Vec<graphics::Rect>>();
+
+        if let Some(index) = DuplicatePolicy::typed_word(&self.game.words, &word_rects, &typed, screen) {
+            let word = &self.game.words[index];
+
+            if self.mistakes.tracks(word, &self.words_pool) {
+                self.mistakes.record_miss(word.label(), review::now());
+                self.word_source.word_result(word.label(), false);
+            }
+        }
+    }
+
+    // scripted words come from the level, the others are picked at random
+    fn spawn_word(&mut self, scripted: Option<ScriptedWord>, ctx: &mut Context) -> GameResult<()> {
+        let difficulty = self.game.game_speed_up;
+        let mut side = movement::pick_side(difficulty, &mut self.game.rng);
+        let lane = self.free_lane(side, ctx);
+
+        if side != SpawnSide::Top && lane.is_none() {
+            if scripted.is_none() {
+                // every lane near the spawn side is taken, try again a bit later
+                self.time_until_next_word = MainState::LANE_RETRY_TIME;
+                return Ok(());
+            }
+
+            // the script doesn't wait, the word comes from the top instead
+            side = SpawnSide::Top;
+        }
+
+        let scripted_word = scripted.as_ref().and_then(|scripted| scripted.word.clone());
+        let random_word = match scripted_word.or_else(|| self.pick_word()) {
+            Some(random_word) => random_word,
+            None => {
+                // the source is out of words or every picked word is already on the screen
+                self.time_until_next_word = MainState::LANE_RETRY_TIME;
+                return Ok(());
+            }
+        };
+
+        let (top_height, bot_height) = self.field_bounds();
+        let prompt = self.word_source.prompt(&random_word);
+        let word_sprite = Box::new(TextSprite::new(prompt.as_ref().unwrap_or(&random_word), ctx, MainState::WORD_TEXT_SIZE)?);
+        let word_width = word_sprite.width(ctx);
+        let lane_top = self.lanes.lane_top(lane.unwrap_or(0));
+
+        let random_point = match side {
+            SpawnSide::Left => Point2 {
+                x: 0.0,
+                y: lane_top
+            },
+            SpawnSide::Right => Point2 {
+                x: self.screen_width - word_width,
+                y: lane_top
+            },
+            SpawnSide::Top => Point2 {
+                x: self.game.rng.gen_range(0.0 .. (self.screen_width - word_width).max(1.0)),
+                y: top_height
+            }
+        };
+
+        let random_speed = match &scripted {
+            Some(scripted) => scripted.speed,
+            None => self.game.rng.gen_range(100.0 .. 300.0)
+        };
+        let velocity = side.velocity(random_speed);
+        let sideways = movement::perpendicular(velocity);
+        let toward_center = (self.screen_width / 2.0 - random_point.x) * sideways.x +
+                            ((top_height + bot_height) / 2.0 - random_point.y) * sideways.y;
+        let pattern = movement::pick_pattern(difficulty, &mut self.game.rng);
+
+        let mut word = Word::new(&random_word, random_point, random_speed, word_sprite, false)?;
+        word.kind = match scripted.map(|scripted| scripted.kind) {
+            Some(WordKind::Splitting) if random_word.chars().count() < WordKind::MIN_SPLIT_LENGTH => WordKind::Normal,
+            Some(kind) => kind,
+            None => WordKind::pick(&random_word, &mut self.game.rng)
+        };
+        if let Some(prompt) = prompt {
+            word.set_prompt(&prompt);
+        }
+        word.set_movement(movement::build_movement(pattern, velocity, toward_center, &mut self.game.rng));
+    
+        let min_word_gen_time = 3.0 - self.game.game_speed_up;
+        let max_word_gen_time = 3.5 - self.game.game_speed_up;
+        self.time_until_next_word = self.game.rng.gen_range(min_word_gen_time .. max_word_gen_time);
+
+        if self.mode == GameMode::Memory {
+            word.visibility_state = Visibility::Flash { seconds: MainState::MEMORY_FLASH_TIME };
+        }
+
+        self.modifiers.on_word_spawned(&mut word, &mut self.game);
+        self.game.words.push(word);
+
+        Ok(())
+    }
+
+    fn pick_word(&mut self) -> Option<String> {
+        self.word_source.set_difficulty(self.game.game_speed_up / MainState::HARDEST_WORDS_SPEED_UP);
+
+        for _ in 0 .. MainState::WORD_PICK_ATTEMPTS {
+            let random_word = self.word_source.next_word()?;
+
+            if self.game.duplicate_policy.allows_spawn(&self.game.words, &random_word) {
+                return Some(random_word);
+            }
+        }
+
+        None
+    }
+
+    fn spawn_boss(&mut self, lane: usize, ctx: &mut Context) -> GameResult<()> {
+        // small or heavily filtered packs make shorter sentences
+        let length = MainState::BOSS_SENTENCE_WORDS.min(self.words_pool.len());
+        let sentence = seq::index::sample(&mut self.game.rng, self.words_pool.len(), length).
+            iter().
+            map(|index| self.words_pool[index].as_str()).
+            collect::<Vec<&str>>().
+            join(" ");
+
+        let pos = Point2 {
+            x: 0.0,
+            y: self.lanes.lane_top(lane)
+        };
+
+        let sprite = Box::new(TextSprite::new(&sentence, ctx, MainState::BOSS_TEXT_SIZE)?);
+        let mut boss = Word::new(&sentence, pos, MainState::BOSS_SPEED, sprite, false)?;
+        boss.kind = WordKind::Boss { lives: MainState::BOSS_LIVES };
+        self.game.words.push(boss);
+
+        Ok(())
+    }
+
+    // columns next to the spawn side where lanes are checked for words, top spawns don't use lanes
+    fn spawn_zone(&self, side: SpawnSide) -> Option<(f32, f32)> {
+        match side {
+            SpawnSide::Left => Some((0.0, MainState::SPAWN_ZONE_WIDTH)),
+            SpawnSide::Right => Some((self.screen_width - MainState::SPAWN_ZONE_WIDTH, self.screen_width)),
+            SpawnSide::Top => None
+        }
+    }
+
+    fn lane_occupancy(&self, side: SpawnSide, ctx: &mut Context) -> Vec<bool> {
+        match self.spawn_zone(side) {
+            Some(spawn_zone) => {
+                let word_rects = self.game.words.iter().map(|word| word.bounding_rect(ctx)).collect::<Vec<graphics::Rect>>();
+                self.lanes.occupied(spawn_zone, &word_rects)
+            },
+            None => vec![false; self.lanes.count()]
+        }
+    }
+
+    fn free_lane(&mut self, side: SpawnSide, ctx: &mut Context) -> Option<usize> {
+        let occupied = self.lane_occupancy(side, ctx);
+        self.lanes.pick_free(&occupied, &mut self.game.rng)
+    }
+
+    fn draw_menu(&mut self, ctx: &mut Context) -> GameResult<()> {
+        let mut title_label = format!("Choose a {}\n(Up/Down) to choose, (Enter) to start\n(Left/Right) Mode: {}\n{}\n(F) Family friendly: {}\n(E) Export missed words",
+                                      if self.mode == GameMode::Campaign { "level" } else { "word pack" }, self.mode.name(), self.mode.description(),
+                                      if self.settings.content_filter { "on" } else { "off" });
+
+        if self.mode == GameMode::Daily {
+            let challenge = self.daily_challenge(Date::today());
+            title_label.push_str(&format!("\nToday: {} with {}, difficulty {}", self.pack_name(&challenge.pack), challenge.modifiers.join(", "), challenge.difficulty));
+            title_label.push_str(&format!("\nPlaying as {}{}", self.daily_profile,
+                                          if self.daily_log.has_ranked(challenge.date, &self.daily_profile) { ", today's ranked run is done" } else { "" }));
+        }
+
+        if self.mode == GameMode::Review {
+            title_label.push_str(&format!("\n{} words due for review", self.mistakes.due_words(review::now()).len()));
+        }
+
+        if self.mode == GameMode::Campaign {
+            title_label.push_str(&format!("\n{} of {} stars", self.campaign.total(), self.levels.len() * levels::MAX_STARS));
+        }
+
+        if let Some(message) = &self.menu_message {
+            title_label.push_str(&format!("\n{}", message));
+        }
+        let mut title = TextSprite::new(&title_label, ctx, MainState::CENTER_PANEL_TEXT_SIZE)?;
+        let mut top_left = Point2 {
+            x: (self.screen_width - title.width(ctx)) / 2.0,
+            y: MainState::MENU_MARGIN
+        };
+
+        title.draw(top_left, graphics::Color::WHITE, ctx)?;
+        top_left.y += title.height(ctx) + MainState::MENU_MARGIN;
+
+        let entries = match self.mode {
+            GameMode::Campaign => self.level_entries(),
+            _ => self.packs.iter().map(|pack| pack.summary()).collect()
+        };
+        let selected = if self.mode == GameMode::Campaign { self.selected_level } else { self.selected_pack };
+
+        for (index, entry) in entries.iter().enumerate() {
+            let color = if index == selected { MainState::POWER_UP_TIMER_COLOR } else { graphics::Color::WHITE };
+            let mut panel = TextSprite::new(entry, ctx, MainState::INFO_PANEL_TEXT_SIZE)?;
+            top_left.x = (self.screen_width - panel.width(ctx)) / 2.0;
+
+            panel.draw(top_left, color, ctx)?;
+            top_left.y += panel.height(ctx) + MainState::MENU_MARGIN;
+        }
+
+        Ok(())
+    }
+
+    // darkens the play field, then shows the typed prefix on the words it continues
+    fn draw_blackout(&mut self, ctx: &mut Context) -> GameResult<()> {
+        let (top_height, bot_height) = self.field_bounds();
+        let field = graphics::Rect::new(0.0, top_height, self.screen_width, bot_height - top_height);
+        draw_helper::draw_rect(field, MainState::BLACKOUT_COLOR, ctx);
+
+        if self.current_input.is_empty() {
+            return Ok(());
+        }
+
+        for word in self.game.words.iter().filter(|word| !word.is_hidden() && word.label().starts_with(&self.current_input)) {
+            let size = if matches!(word.kind, WordKind::Boss { .. }) { MainState::BOSS_TEXT_SIZE } else { MainState::WORD_TEXT_SIZE };
+            let mut prefix = TextSprite::new(&self.current_input, ctx, size)?;
+            prefix.draw(word.pos, word.kind.color(), ctx)?;
+        }
+
+        Ok(())
+    }
+
+    // the level-select list, locked levels don't show their name
+    fn level_entries(&self) -> Vec<String> {
+        self.levels.iter().enumerate().
+            map(|(index, level)| match self.campaign.is_unlocked(&self.levels, index) {
+                true => format!("{}. {}  {}\n{}", index + 1, level.name, levels::star_label(self.campaign.stars(&level.id)), level.description),
+                false => format!("{}. Locked", index + 1)
+            }).
+            collect()
+    }
+
+    // today's ranked results and the month of the player's daily history
+    fn draw_daily_results(&mut self, ctx: &mut Context) -> GameResult<()> {
+        let date = match &self.daily {
+            Some(challenge) => challenge.date,
+            None => return Ok(())
+        };
+
+        let mut results_label = format!("Daily challenge {}\n{}\n(Enter) back to the menu\n\nToday:", date,
+                                        if self.daily_ranked { "Your ranked result is saved" } else { "You already played today, this run isn't ranked" });
+        for result in self.daily_log.day_results(date) {
+            results_label.push_str(&format!("\n{} {:.2}", result.profile, result.score));
+        }
+
+        let mut results_panel = TextSprite::new(&results_label, ctx, MainState::CENTER_PANEL_TEXT_SIZE)?;
+        let top_left = Point2 {
+            x: (self.screen_width - results_panel.width(ctx)) / 2.0,
+            y: MainState::MENU_MARGIN
+        };
+        results_panel.draw(top_left, graphics::Color::WHITE, ctx)?;
+
+        self.draw_calendar(date, top_left.y + results_panel.height(ctx) + MainState::MENU_MARGIN, ctx)
+    }
+
+    fn draw_calendar(&self, date: Date, top: f32, ctx: &mut Context) -> GameResult<()> {
+        let history = self.daily_log.history(&self.daily_profile);
+        let left = (self.screen_width - 7.0 * MainState::CALENDAR_CELL_WIDTH) / 2.0;
+
+        let title_label = format!("{} {}, {}", daily::MONTH_NAMES[date.month as usize - 1], date.year, self.daily_profile);
+        let mut title = TextSprite::new(&title_label, ctx, MainState::INFO_PANEL_TEXT_SIZE)?;
+        let mut top_left = Point2 { x: (self.screen_width - title.width(ctx)) / 2.0, y: top };
+        title.draw(top_left, graphics::Color::WHITE, ctx)?;
+        top_left.y += title.height(ctx);
+
+        for (column, name) in daily::WEEKDAY_NAMES.iter().enumerate() {
+            let mut cell = TextSprite::new(name, ctx, MainState::INFO_PANEL_TEXT_SIZE)?;
+            cell.draw(Point2 { x: left + column as f32 * MainState::CALENDAR_CELL_WIDTH, y: top_left.y }, graphics::Color::WHITE, ctx)?;
+        }
+        top_left.y += MainState::CALENDAR_CELL_HEIGHT / 2.0;
+
+        // played days show their score
+        for week in daily::month_weeks(date.year, date.month) {
+            for (column, day) in week.iter().enumerate() {
+                let day = match day {
+                    Some(day) => *day,
+                    None => continue
+                };
+
+                let (label, color) = match history.get(&Date { day, ..date }) {
+                    Some(score) => (format!("{}\n{:.0}", day, score), MainState::POWER_UP_TIMER_COLOR),
+                    None => (day.to_string(), graphics::Color::WHITE)
+                };
+
+                let mut cell = TextSprite::new(&label, ctx, MainState::INFO_PANEL_TEXT_SIZE)?;
+                cell.draw(Point2 { x: left + column as f32 * MainState::CALENDAR_CELL_WIDTH, y: top_left.y }, color, ctx)?;
+            }
+
+            top_left.y += MainState::CALENDAR_CELL_HEIGHT;
+        }
+
+        Ok(())
+    }
+
+    // vertical range between the top and bottom panels where words can spawn
+    fn field_bounds(&self) -> (f32, f32) {
+        MainState::field_bounds_for(self.screen_height)
+    }
+
+    fn field_bounds_for(screen_height: f32) -> (f32, f32) {
+        let margin = 10.0;
+        let top_height = MainState::TOP_PANEL_TEXT_SIZE + margin;
+        let bot_height = screen_height - MainState::BOT_PANEL_TEXT_SIZE - margin;
+
+        (top_height, bot_height)
+    }
 }
 
 impl event::EventHandler for MainState {
     fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
-        if self.game_over {
+        const FPS_CAP: u32 = 60;
+
+        if self.in_menu {
+            // drop the time spent in the menu, so the game doesn't try to catch up on it
+            while timer::check_update_time(ctx, FPS_CAP) {}
             return Ok(())
         }
 
-        const FPS_CAP: u32 = 60;
+        if self.game_over {
+            return Ok(())
+        }
 
         while timer::check_update_time(ctx, FPS_CAP)
         {
             let seconds = 1.0 / (FPS_CAP as f32);
 
-            // Screen shaker
-            self.time_until_shake -= seconds;
-            if self.time_until_shake <= 0.0 {
-                self.time_until_shake = self.rng.gen_range(5.0 .. 20.0);
-                self.shake_screen = true;
+            self.power_ups.update(seconds, &mut self.game);
+
+            if let Some(wave) = self.level_run.as_mut().and_then(|run| run.take_started_wave()) {
+                // every wave starts with only its own modifiers
+                self.modifiers.stop(&mut self.game);
+                self.modifiers = ModifierRegistry::with_defaults();
+                self.modifiers.keep(&wave.modifiers);
+                self.modifiers.start_now();
             }
 
-            if self.shake_screen {
-                self.shake_time -= seconds;
-                if self.shake_time <= 0.0 {
-                    self.shake_time = MainState::SHAKE_DURATION;
-                    self.shake_screen = false;
+            self.modifiers.update(seconds, &mut self.game);
+
+            self.events.update(seconds, &mut self.game);
+
+            // Spawn words, world events can change how often
+            let spawn_seconds = seconds * self.game.spawn_rate;
+            match self.level_run.as_mut() {
+                Some(run) => {
+                    if let Some(scripted) = run.update(spawn_seconds, &mut self.game.rng) {
+                        self.spawn_word(Some(scripted), ctx)?;
+                    }
+                },
+                None => {
+                    self.time_until_next_word -= spawn_seconds;
+                    if self.time_until_next_word <= 0.0 {
+                        self.spawn_word(None, ctx)?;
+                    }
                 }
             }
 
-            // Spawn words
-            self.time_until_next_word -= seconds;
-            if self.time_until_next_word <= 0.0 {
-                let margin = 10.0;
-                let top_height = MainState::TOP_PANEL_TEXT_SIZE + margin;
-                let bot_height = self.screen_height - MainState::BOT_PANEL_TEXT_SIZE - margin;
-                let random_point = Point2 {
-                    x: 0.0,
-                    y: self.rng.gen_range(top_height .. bot_height)
-                };
-            
-                let random_word = self.words_pool[self.rng.gen_range(0 .. self.words_pool.len())].clone();
-                
-                let random_speed = self.rng.gen_range(100.0 .. 300.0);
-                let percentage: u8 = self.rng.gen_range(0 ..= 100);
-                let is_color_changing = percentage < 30;
-                let word_sprite = Box::new(TextSprite::new(&random_word, ctx, 32.0)?);
-                let word = Word::new(&random_word, random_point, random_speed, word_sprite, is_color_changing)?;
-    
-                self.words.push(word);
-                let min_word_gen_time = 3.0 - self.game_speed_up;
-                let max_word_gen_time = 3.5 - self.game_speed_up;
-                self.time_until_next_word = self.rng.gen_range(min_word_gen_time .. max_word_gen_time);
-                self.game_speed_up += 0.03;
+            // Spawn boss sentences
+            self.time_until_boss -= seconds;
+            if self.time_until_boss <= 0.0 && self.mode.spawns_bosses() {
+                match self.free_lane(SpawnSide::Left, ctx) {
+                    // no words to make a sentence of
+                    _ if self.words_pool.is_empty() => self.time_until_boss = MainState::BOSS_INTERVAL,
+                    Some(lane) => {
+                        self.time_until_boss = MainState::BOSS_INTERVAL;
+                        self.spawn_boss(lane, ctx)?;
+                    },
+                    None => self.time_until_boss = MainState::LANE_RETRY_TIME
+                }
             }
 
-            for word in self.words.iter_mut() {
-                word.update(seconds);
-    
-                if word.label() == self.current_input {
-                    word.is_typed = true;
-                    
-                    self.score += word.get_reward();
-                    self.cash += word.get_reward();
+            let word_seconds = seconds * self.game.word_time_scale();
+            let mut split_words = Vec::new();
+            for word in self.game.words.iter_mut() {
+                word.update(word_seconds);
+                word.update_shown_label(&self.current_input);
+            }
 
-                    self.assets.word_typed_sound.set_volume(self.sound_volume);
-                    let _ = self.assets.word_typed_sound.play(ctx);
+            let screen = graphics::Rect::new(0.0, 0.0, self.screen_width, self.screen_height);
+            let word_rects = self.game.words.iter().map(|word| word.bounding_rect(ctx)).collect::<Vec<graphics::Rect>>();
+            let targets = self.game.duplicate_policy.targets(&self.game.words, &word_rects, &self.current_input, screen);
+            let now = review::now();
 
-                    // clear the input field after successfully typed word
-                    self.current_input = String::new();
+            for &index in targets.iter() {
+                let word = &mut self.game.words[index];
+
+                if word.hit() {
+                    self.mistakes.record_success(word.label(), now);
+                    self.word_source.word_result(word.label(), true);
+                    self.game.combo.hit();
+                    let mut reward = self.game.economy.reward(word, &self.game.combo, KillCause::Typed);
+                    reward.cash *= self.game.cash_multiplier;
+
+                    // the source has already counted the word in its chain
+                    if let Some(chain) = self.word_source.chain() {
+                        reward.score *= chain.multiplier();
+                    }
+
+                    self.game.score += reward.score;
+                    self.game.cash += reward.cash;
+
+                    let popup_label = format!("+{:.2} x{:.1}", reward.score, self.game.combo.multiplier());
+                    let popup_sprite = Box::new(TextSprite::new(&popup_label, ctx, MainState::POPUP_TEXT_SIZE)?);
+                    self.popups.push(Popup::new(word.pos, popup_sprite));
+
+                    if let Some(halves) = word.split_labels() {
+                        split_words.push((halves, word.pos, word.speed(), word.heading()));
+                    }
                 }
+            }
 
-                if word.pos.x >= self.screen_width {
+            if !targets.is_empty() {
+                self.assets.word_typed_sound.set_volume(self.sound_volume);
+                let _ = self.assets.word_typed_sound.play(ctx);
+
+                // clear the input field after successfully typed word
+                self.current_input = String::new();
+            }
+
+            for (word, word_rect) in self.game.words.iter_mut().zip(word_rects) {
+                if !word.is_typed && word.heading().has_escaped(word_rect, screen) {
                     word.is_typed = true;
+                    let escape_cost = word.escape_cost();
+
+                    if word.is_hidden() {
+                        let sprite = Box::new(TextSprite::new(word.label(), ctx, MainState::WORD_TEXT_SIZE)?);
+                        let pos = Point2 {
+                            x: word.pos.x.clamp(0.0, (self.screen_width - word_rect.w).max(0.0)),
+                            y: word.pos.y.clamp(0.0, (self.screen_height - word_rect.h).max(0.0))
+                        };
+                        self.reveals.push(Popup::new(pos, sprite));
+                    }
+
+                    if self.mode.logs_mistakes() && self.mistakes.tracks(word, &self.words_pool) {
+                        self.mistakes.record_miss(word.label(), now);
+                        self.word_source.word_result(word.label(), false);
+                    }
 
-                    if !debug::is_active() {
+                    if escape_cost > 0 {
+                        self.game.combo.reset();
+                    }
+
+                    if escape_cost > 0 && self.game.shield_active {
+                        // the shield takes the hit instead of the player
+                        self.game.shield_active = false;
+                    }
+                    else if escape_cost > 0 && !debug::is_active() {
                         // don't end the game when debug is active
-                        self.remaining_lifes -= 1;
+                        self.game.remaining_lifes = self.game.remaining_lifes.saturating_sub(escape_cost);
 
-                        if self.remaining_lifes == 0 {
+                        if self.game.remaining_lifes == 0 {
                             self.game_over = true;
                         }
                     }
                 }
             }
 
-            self.words.retain(|word| !word.is_typed);
-        }
+            self.game.words.retain(|word| !word.is_typed);
 
-        Ok(())
-    }
+            // a level is cleared once its last wave is over and no word is left
+            let cleared = self.level_run.as_ref().filter(|run| run.is_finished() && self.game.words.is_empty() && !self.game_over);
+            if let Some(run) = cleared {
+                let stars = run.level().stars_for(self.game.score);
 
-    fn key_down_event(&mut self, ctx: &mut Context, keycode: event::KeyCode, _keymods: event::KeyMods, _repeat: bool) {
-        
-        match keycode {
-            event::KeyCode::Escape => event::quit(ctx),
-            event::KeyCode::Key1 |
-            event::KeyCode::Numpad1 => {
-                if self.cash >= MainState::BUY_LIFE_TAX {
-                    self.cash -= MainState::BUY_LIFE_TAX;
-                    self.remaining_lifes += 1;
+                if self.campaign.record(&run.level().id, stars) {
+                    filesystem_helper::save_campaign(ctx, &self.campaign);
                 }
-            },
-            event::KeyCode::Key2 |
-            event::KeyCode::Numpad2 => {
-                if self.cash >= MainState::REMOVE_WORDS_TAX && self.words.len() > 0 {
-                    self.cash -= MainState::REMOVE_WORDS_TAX;
 
-                    if self.words.len() <= MainState::REMOVE_WORDS_COUNT {
-                        self.words.iter_mut().for_each(|word| {
-                            word.is_typed = true;
-                            self.score += word.get_reward();
-                        });
+                self.level_stars = Some(stars);
+                self.game_over = true;
+            }
+
+            if self.game_over {
+                self.finish_session(ctx);
+                break;
+            }
+
+            for popup in self.popups.iter_mut() {
+                popup.update(seconds);
+            }
+
+            self.popups.retain(|popup| !popup.is_expired());
+
+            for reveal in self.reveals.iter_mut() {
+                reveal.update(seconds);
+            }
+
+            self.reveals.retain(|reveal| !reveal.is_expired());
+
+            // the halves of a split word continue side by side
+            for ((first, second), pos, speed, heading) in split_words {
+                for (label, offset) in [(first, -MainState::SPLIT_OFFSET), (second, MainState::SPLIT_OFFSET)] {
+                    if !self.game.duplicate_policy.allows_spawn(&self.game.words, &label) {
+                        continue;
                     }
-                    else {
-                        let sample_indexes = seq::index::sample(&mut self.rng, self.words.len(), MainState::REMOVE_WORDS_COUNT);
 
-                        for index in sample_indexes.iter() {
-                            self.words[index].is_typed = true;
-                            self.score += self.words[index].get_reward();
-                        }
+                    let sprite = Box::new(TextSprite::new(&label, ctx, MainState::WORD_TEXT_SIZE)?);
+                    let split_pos = Point2 { x: pos.x, y: pos.y + offset };
+                    let mut split_word = Word::new(&label, split_pos, speed, sprite, false)?;
+                    split_word.set_movement(Box::new(Linear { velocity: heading.velocity(speed) }));
+
+                    if self.mode == GameMode::Memory {
+                        split_word.visibility_state = Visibility::Flash { seconds: MainState::MEMORY_FLASH_TIME };
                     }
+
+                    self.game.words.push(split_word);
                 }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn key_down_event(&mut self, ctx: &mut Context, keycode: event::KeyCode, keymods: event::KeyMods, _repeat: bool) {
+        if self.in_menu {
+            let packs_count = self.packs.len();
+
+            match keycode {
+                event::KeyCode::Escape => event::quit(ctx),
+                event::KeyCode::Up if self.mode == GameMode::Campaign => self.selected_level = self.selected_level.saturating_sub(1),
+                event::KeyCode::Down if self.mode == GameMode::Campaign => self.selected_level = (self.selected_level + 1).min(self.levels.len().saturating_sub(1)),
+                event::KeyCode::Up => self.selected_pack = (self.selected_pack + packs_count - 1) % packs_count,
+                event::KeyCode::Down => self.selected_pack = (self.selected_pack + 1) % packs_count,
+                event::KeyCode::Left => self.mode = self.mode.previous(),
+                event::KeyCode::Right => self.mode = self.mode.next(),
+                event::KeyCode::Return => self.start_game(ctx).unwrap(),
+                event::KeyCode::F => {
+                    self.settings.content_filter ^= true;
+                    filesystem_helper::save_settings(ctx, &self.settings);
+                },
+                event::KeyCode::E => {
+                    self.menu_message = Some(match filesystem_helper::export_mistakes(ctx, &self.mistakes) {
+                        Ok(path) => format!("Missed words exported to {}", path.display()),
+                        Err(error) => format!("Couldn't export the missed words: {}", error)
+                    });
+                },
+                _ => ()
+            }
+
+            return;
+        }
+
+        let digit = digit_key(keycode).filter(|_| self.mode.types_digits());
+
+        if let Some(index) = self.power_ups.find_by_key(keycode).filter(|_| self.mode.has_power_ups()) {
+            if keymods.contains(event::KeyMods::SHIFT) {
+                self.power_ups.buy(index, &mut self.game);
+            }
+            else {
+                self.power_ups.activate(index, &mut self.game);
+            }
+
+            return;
+        }
+
+        let input_length = self.current_input.len();
+
+        match keycode {
+            event::KeyCode::Escape => {
+                self.finish_session(ctx);
+                event::quit(ctx);
             },
-            event::KeyCode::Key3 |
-            event::KeyCode::Numpad3 => {
-                if self.cash >= MainState::SLOW_WORD_SPAWN_TAX {
-                    self.cash -= MainState::SLOW_WORD_SPAWN_TAX;
-                    self.game_speed_up /= 2.0;
-                }
-            },
-            event::KeyCode::NumpadAdd => {
-                if self.sound_volume + MainState::SOUND_VOLUME_STEP <= 100.0 {
-                    self.sound_volume += MainState::SOUND_VOLUME_STEP;
-                    self.assets.background_music.set_volume(self.sound_volume);
-                }
+            event::KeyCode::NumpadAdd if self.sound_volume + MainState::SOUND_VOLUME_STEP <= 100.0 => {
+                self.sound_volume += MainState::SOUND_VOLUME_STEP;
+                self.assets.background_music.set_volume(self.sound_volume);
             },
-            event::KeyCode::NumpadSubtract => {
-                if self.sound_volume - MainState::SOUND_VOLUME_STEP >= 0.0 {
-                    self.sound_volume -= MainState::SOUND_VOLUME_STEP;
-                    self.assets.background_music.set_volume(self.sound_volume);
-                }
+            event::KeyCode::NumpadSubtract if self.sound_volume - MainState::SOUND_VOLUME_STEP >= 0.0 => {
+                self.sound_volume -= MainState::SOUND_VOLUME_STEP;
+                self.assets.background_music.set_volume(self.sound_volume);
             },
             event::KeyCode::Grave => {
                 self.show_info ^= true;
             }
+            // daily runs keep the default policy
+            event::KeyCode::Tab if self.daily.is_none() => {
+                self.game.duplicate_policy = self.game.duplicate_policy.next();
+            },
             event::KeyCode::Minus => {
                 self.current_input += "-";
             },
-            event::KeyCode::Return => {
-                if !self.saved_score {
-                    self.scoreboard = filesystem_helper::save_score(ctx, self.current_input.clone(), self.score, MainState::SCOREBOARD_SIZE);
-                    self.current_input = String::new();
-                    self.saved_score = true;
+            event::KeyCode::Space => {
+                self.current_input += " ";
+            },
+            event::KeyCode::Return if !self.saved_score => {
+                // the daily result was saved with the session, for the profile
+                if self.daily.is_none() {
+                    self.scoreboard = filesystem_helper::save_score(ctx, &self.scoreboard_id(), self.current_input.clone(), self.game.score, MainState::SCOREBOARD_SIZE);
                 }
+
+                self.current_input = String::new();
+                self.saved_score = true;
             },
+            event::KeyCode::Return if self.game_over => self.return_to_menu(),
             event::KeyCode::A => {
                 self.current_input = check_shift_pressed(self.current_input.clone(), ctx, "a", "A")
             },
@@ -381,8 +1270,23 @@ impl event::EventHandler for MainState {
             event::KeyCode::Back => {
                 self.current_input.pop();
             },
+            _ if digit.is_some() => {
+                self.current_input.extend(digit);
+            },
             _ => ()
         }
+
+        // a letter which doesn't continue any word on the screen is a typo
+        if !self.game_over && self.current_input.len() > input_length {
+            let correct = self.game.words.iter().any(|word| word.label().starts_with(&self.current_input));
+
+            if !correct {
+                self.game.combo.reset();
+                self.record_typo(ctx);
+            }
+
+            self.record_key(correct);
+        }
     }
 
     fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
@@ -391,14 +1295,20 @@ impl event::EventHandler for MainState {
 
         let label_margin = 10.0;
         let game_status_panel_color = graphics::Color::WHITE;
+
+        if self.in_menu {
+            self.draw_menu(ctx)?;
+            graphics::present(ctx)?;
+            return Ok(())
+        }
         let mut shake_translation: Point2<f32> = Point2 {
             x: 0.0,
             y: 0.0
         };
 
-        if self.shake_screen {
-            let dx = self.rng.gen_range(-MainState::SHAKE_MAGNITUDE ..=MainState::SHAKE_MAGNITUDE);
-            let dy = self.rng.gen_range(-MainState::SHAKE_MAGNITUDE ..=MainState::SHAKE_MAGNITUDE);
+        if self.game.shake_screen {
+            let dx = self.shake_rng.gen_range(-MainState::SHAKE_MAGNITUDE ..=MainState::SHAKE_MAGNITUDE);
+            let dy = self.shake_rng.gen_range(-MainState::SHAKE_MAGNITUDE ..=MainState::SHAKE_MAGNITUDE);
 
             shake_translation.x = dx;
             shake_translation.y = dy;
@@ -425,20 +1335,41 @@ impl event::EventHandler for MainState {
 
             if !self.saved_score {
                 let ending;
-                if self.score < 100.0 {
+                if self.game.score < 100.0 {
                     ending = "Bummer, I know you can do better :) Try again!";
                 }
-                else if self.score >= 100.0 && self.score < 500.0 {
+                else if self.game.score >= 100.0 && self.game.score < 500.0 {
                     ending = "Not very bad!";
                 }
-                else if self.score >= 500.0 && self.score < 1000.0 {
+                else if self.game.score >= 500.0 && self.game.score < 1000.0 {
                     ending = "Amazing, but can you do better?"
                 }
                 else {
                     ending = "You're a madman, niiice :)"
                 }
 
-                let game_over_label = format!("Game over!\nYour score is : {:.2}\n{}\nType username for the scoreboard!", self.score, ending);
+                let next_step = if self.daily.is_some() { "(Enter) to see today's results" } else { "Type username for the scoreboard!" };
+                let mut game_over_label = format!("Game over!\nYour score is : {:.2}\n{}\n{}", self.game.score, ending, next_step);
+
+                if !self.improvements.is_empty() {
+                    game_over_label.push_str("\n\nKeys you improved:");
+
+                    for improvement in self.improvements.iter().take(MainState::SHOWN_IMPROVEMENTS) {
+                        game_over_label.push_str(&format!("\n{}", improvement.describe()));
+                    }
+                }
+
+                if let Some(run) = &self.level_run {
+                    let result = match self.level_stars {
+                        Some(stars) => format!("cleared {}", levels::star_label(stars)),
+                        None => format!("lost in wave {} of {}", run.wave_number(), run.level().waves.len())
+                    };
+                    game_over_label.push_str(&format!("\n\n{} {}", run.level().name, result));
+                }
+
+                if let Some(code) = self.challenge_code() {
+                    game_over_label.push_str(&format!("\n\nChallenge code: {}", code));
+                }
                 let mut game_over_panel = TextSprite::new(&game_over_label, ctx, MainState::CENTER_PANEL_TEXT_SIZE).unwrap();
 
                 let centered = Point2 {
@@ -448,8 +1379,22 @@ impl event::EventHandler for MainState {
 
                 game_over_panel.draw(centered, game_status_panel_color, ctx).unwrap();
             }
+            else if self.daily.is_some() {
+                self.draw_daily_results(ctx)?;
+            }
             else {
-                let scoreboard_label = format!("Scoreboard:\n{}", draw_helper::format_scoreboard(&self.scoreboard));
+                let mut scoreboard_title = match &self.level_run {
+                    Some(run) => run.level().name.clone(),
+                    None => self.packs[self.selected_pack].manifest.name.clone()
+                };
+                if self.mode.has_own_scoreboard() {
+                    scoreboard_title.push_str(&format!(", {}", self.mode.name()));
+                }
+
+                let mut scoreboard_label = format!("Scoreboard ({}):\n{}\n(Enter) back to the menu", scoreboard_title, draw_helper::format_scoreboard(&self.scoreboard));
+                if let Some(code) = self.challenge_code() {
+                    scoreboard_label.push_str(&format!("\nChallenge code: {}", code));
+                }
                 let mut scoreboard_panel = TextSprite::new(&scoreboard_label, ctx, MainState::CENTER_PANEL_TEXT_SIZE).unwrap();
 
                 let centered = Point2 {
@@ -486,8 +1431,8 @@ impl event::EventHandler for MainState {
 
         draw_helper::translate(&mut top_left, &shake_translation);
 
-        let options_label = format!("(`) for Info|");
-        let mut options_panel = TextSprite::new(&options_label, ctx, MainState::TOP_PANEL_TEXT_SIZE).unwrap();
+        let options_label = "(`) for Info|";
+        let mut options_panel = TextSprite::new(options_label, ctx, MainState::TOP_PANEL_TEXT_SIZE).unwrap();
         top_left.x += label_margin;
         options_panel.draw(top_left, game_status_panel_color, ctx).unwrap();
         top_left.x += options_panel.width(ctx);
@@ -496,6 +1441,49 @@ impl event::EventHandler for MainState {
         let mut current_volume_panel = TextSprite::new(&current_volume_label, ctx, MainState::TOP_PANEL_TEXT_SIZE).unwrap();
         top_left.x += label_margin;
         current_volume_panel.draw(top_left, game_status_panel_color, ctx).unwrap();
+        top_left.x += current_volume_panel.width(ctx);
+
+        let duplicates_label = format!("|Duplicates: {}", self.game.duplicate_policy.name());
+        let mut duplicates_panel = TextSprite::new(&duplicates_label, ctx, MainState::TOP_PANEL_TEXT_SIZE).unwrap();
+        top_left.x += label_margin;
+        duplicates_panel.draw(top_left, game_status_panel_color, ctx).unwrap();
+        top_left.x += duplicates_panel.width(ctx);
+
+        if let Some(chain) = self.word_source.chain() {
+            let mut chain_label = format!("|Chain: {} x{:.1}", chain.length(), chain.multiplier());
+
+            if let Some(letter) = chain.next_letter() {
+                chain_label.push_str(&format!(" next: {}", letter));
+            }
+
+            let mut chain_panel = TextSprite::new(&chain_label, ctx, MainState::TOP_PANEL_TEXT_SIZE).unwrap();
+            top_left.x += label_margin;
+            chain_panel.draw(top_left, game_status_panel_color, ctx).unwrap();
+            top_left.x += chain_panel.width(ctx);
+        }
+
+        if let Some(run) = &self.level_run {
+            let mut wave_panel = TextSprite::new(&format!("|Wave: {}/{}", run.wave_number(), run.level().waves.len()), ctx, MainState::TOP_PANEL_TEXT_SIZE).unwrap();
+            top_left.x += label_margin;
+            wave_panel.draw(top_left, game_status_panel_color, ctx).unwrap();
+            top_left.x += wave_panel.width(ctx);
+        }
+
+        if let Some((event, remaining)) = self.events.running() {
+            let mut event_panel = TextSprite::new(&format!("|{}: {:.1}s", event.name(), remaining), ctx, MainState::TOP_PANEL_TEXT_SIZE).unwrap();
+            top_left.x += label_margin;
+            event_panel.draw(top_left, MainState::EVENT_COLOR, ctx).unwrap();
+            top_left.x += event_panel.width(ctx);
+        }
+
+        // Draw running buff timers
+        for (index, remaining) in self.power_ups.active_timers() {
+            let timer_label = format!("|{}: {:.1}s", self.power_ups.power_ups()[index].hud_label(), remaining);
+            let mut timer_panel = TextSprite::new(&timer_label, ctx, MainState::TOP_PANEL_TEXT_SIZE).unwrap();
+            top_left.x += label_margin;
+            timer_panel.draw(top_left, MainState::POWER_UP_TIMER_COLOR, ctx).unwrap();
+            top_left.x += timer_panel.width(ctx);
+        }
 
         // Draw current cash
         let mut bottom_right = Point2 {
@@ -505,7 +1493,7 @@ impl event::EventHandler for MainState {
 
         draw_helper::translate(&mut bottom_right, &shake_translation);
 
-        let cash_label = format!("Cash: {:.2}", self.cash);
+        let cash_label = format!("Cash: {:.2}", self.game.cash);
         let mut cash_panel = TextSprite::new(&cash_label, ctx, MainState::BOT_PANEL_TEXT_SIZE).unwrap();
         bottom_right.x -= cash_panel.width(ctx) + label_margin;
         bottom_right.y -= cash_panel.height(ctx);
@@ -513,7 +1501,7 @@ impl event::EventHandler for MainState {
         bottom_right.y += cash_panel.height(ctx);
 
         // Draw remaining lifes
-        let lifes_label = format!("Lifes: {}", self.remaining_lifes);
+        let lifes_label = format!("Lifes: {}", self.game.remaining_lifes);
         let mut lifes_panel = TextSprite::new(&lifes_label, ctx, MainState::BOT_PANEL_TEXT_SIZE).unwrap();
         bottom_right.x -= lifes_panel.width(ctx) + label_margin;
         bottom_right.y -= lifes_panel.height(ctx);
@@ -521,12 +1509,11 @@ impl event::EventHandler for MainState {
         bottom_right.y += lifes_panel.height(ctx);
 
         // Draw current score
-        let score_label = format!("Score: {:.2}", self.score);
+        let score_label = format!("Score: {:.2}", self.game.score);
         let mut score_panel = TextSprite::new(&score_label, ctx, MainState::BOT_PANEL_TEXT_SIZE).unwrap();
         bottom_right.x -= score_panel.width(ctx) + label_margin;
         bottom_right.y -= score_panel.height(ctx);
         score_panel.draw(bottom_right, game_status_panel_color, ctx).unwrap();
-        bottom_right.y += score_panel.height(ctx);
 
         // Draw power ups
         let power_up_color = graphics::Color::WHITE;
@@ -537,35 +1524,99 @@ impl event::EventHandler for MainState {
 
         draw_helper::translate(&mut top_right, &shake_translation);
 
-        if self.cash >= MainState::SLOW_WORD_SPAWN_TAX {
-            top_right.x -= self.power_up_panels[0].width(ctx) + label_margin;
-            self.power_up_panels[0].draw(top_right, power_up_color, ctx).unwrap();
-        }
+        // panels are laid out right to left, so the last registered buff ends up rightmost
+        for index in (0 .. self.power_ups.power_ups().len()).rev() {
+            let owned = self.power_ups.owned(index);
 
-        if self.cash >= MainState::REMOVE_WORDS_TAX {
-            top_right.x -= self.power_up_panels[1].width(ctx) + label_margin;
-            self.power_up_panels[1].draw(top_right, power_up_color, ctx).unwrap();
-        }
+            // modes which type digits can't use the buffs, so they aren't shown
+            if self.mode.has_power_ups() && (owned > 0 || self.power_ups.can_afford(index, &self.game)) {
+                let power_up = &self.power_ups.power_ups()[index];
+                let label = format!("({}) {} x{} ({:.2}$)", power_up.key_label(), power_up.hud_label(), owned, self.power_ups.price(index));
+                let mut panel = TextSprite::new(&label, ctx, MainState::TOP_PANEL_TEXT_SIZE).unwrap();
+
+                // start a new row when the panel would run into the top left labels
+                if top_right.x - panel.width(ctx) - label_margin < top_left.x + label_margin {
+                    top_right.x = self.screen_width + shake_translation.x;
+                    top_right.y += MainState::TOP_PANEL_TEXT_SIZE;
+                }
 
-        if self.cash >= MainState::BUY_LIFE_TAX {
-            top_right.x -= self.power_up_panels[2].width(ctx) + label_margin;
-            self.power_up_panels[2].draw(top_right, power_up_color, ctx).unwrap();
+                top_right.x -= panel.width(ctx) + label_margin;
+                panel.draw(top_right, power_up_color, ctx).unwrap();
+
+                let cooldown_progress = self.power_ups.cooldown_progress(index);
+                if cooldown_progress > 0.0 {
+                    let bar_top_left = Point2 {
+                        x: top_right.x,
+                        y: top_right.y + panel.height(ctx)
+                    };
+
+                    draw_helper::draw_progress_bar(bar_top_left, panel.width(ctx), MainState::COOLDOWN_BAR_HEIGHT, cooldown_progress, power_up_color, ctx);
+                }
+            }
         }
 
-        for word in self.words.iter_mut() {
+        for word in self.game.words.iter_mut() {
             word.translate(shake_translation);
 
-            if !self.shake_screen {
+            if !self.game.shake_screen {
                 word.reset_translation();
             }
 
             word.draw(ctx)?;
         }
 
+        // Draw fog over the words
+        let (top_height, bot_height) = self.field_bounds();
+        for fog_band in self.game.fog_bands.iter() {
+            let fog_rect = graphics::Rect::new(
+                0.0,
+                top_height + fog_band.top * (bot_height - top_height),
+                self.screen_width,
+                fog_band.height * (bot_height - top_height));
+
+            draw_helper::draw_rect(fog_rect, MainState::FOG_COLOR, ctx);
+        }
+
+        for popup in self.popups.iter_mut() {
+            popup.draw(MainState::POPUP_COLOR, ctx)?;
+        }
+
+        for reveal in self.reveals.iter_mut() {
+            reveal.draw(MainState::REVEAL_COLOR, ctx)?;
+        }
+
+        if self.game.blackout {
+            self.draw_blackout(ctx)?;
+        }
+
+        // Draw the warning of the next world event
+        if let Some((event, remaining)) = self.events.warning() {
+            let warning_label = format!("{} in {:.0}s!\n{}", event.name(), remaining.ceil(), event.description());
+            let mut warning_panel = TextSprite::new(&warning_label, ctx, MainState::CENTER_PANEL_TEXT_SIZE)?;
+            let top_left = Point2 {
+                x: (self.screen_width - warning_panel.width(ctx)) / 2.0,
+                y: MainState::MENU_MARGIN * 2.0
+            };
+
+            draw_helper::draw_text_background(top_left, warning_panel.width(ctx), warning_panel.height(ctx), 10.0, MainState::WARNING_BACKGROUND_COLOR, ctx);
+            warning_panel.draw(top_left, MainState::EVENT_COLOR, ctx)?;
+        }
+
         if debug::is_active() {
-            for word in &mut self.words {
+            for word in &mut self.game.words {
                 debug::draw_outline(word.bounding_rect(ctx), ctx).unwrap();
             }
+
+            // occupied lanes next to the left and right spawn sides
+            for side in [SpawnSide::Left, SpawnSide::Right] {
+                let (left, right) = self.spawn_zone(side).unwrap();
+
+                for (index, occupied) in self.lane_occupancy(side, ctx).into_iter().enumerate() {
+                    if occupied {
+                        debug::draw_outline(self.lanes.lane_rect(index, left, right), ctx).unwrap();
+                    }
+                }
+            }
         }
 
         graphics::present(ctx)?;
@@ -573,6 +1624,29 @@ impl event::EventHandler for MainState {
     }
 }
 
+// the options saved in a challenge code, which are never that large
+fn byte(value: usize) -> u8 {
+    value.min(u8::MAX as usize) as u8
+}
+
+fn digit_key(keycode: event::KeyCode) -> Option<char> {
+    let digit = match keycode {
+        event::KeyCode::Key0 | event::KeyCode::Numpad0 => 0,
+        event::KeyCode::Key1 | event::KeyCode::Numpad1 => 1,
+        event::KeyCode::Key2 | event::KeyCode::Numpad2 => 2,
+        event::KeyCode::Key3 | event::KeyCode::Numpad3 => 3,
+        event::KeyCode::Key4 | event::KeyCode::Numpad4 => 4,
+        event::KeyCode::Key5 | event::KeyCode::Numpad5 => 5,
+        event::KeyCode::Key6 | event::KeyCode::Numpad6 => 6,
+        event::KeyCode::Key7 | event::KeyCode::Numpad7 => 7,
+        event::KeyCode::Key8 | event::KeyCode::Numpad8 => 8,
+        event::KeyCode::Key9 | event::KeyCode::Numpad9 => 9,
+        _ => return None
+    };
+
+    char::from_digit(digit, 10)
+}
+
 fn check_shift_pressed(current_input: String, ctx: &mut Context, lower_letter: &str, upper_letter: &str) -> String {
     if is_key_pressed(ctx, event::KeyCode::LShift) ||
        is_key_pressed(ctx, event::KeyCode::RShift) {
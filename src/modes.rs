@@ -0,0 +1,120 @@
+// the rules a game is played with, picked in the menu
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum GameMode {
+    #[default]
+    Classic,
+    // words with the player's weak keys show up more often
+    Adaptive,
+    // missed words come back until they are mastered
+    Review,
+    // the words show a prompt and the player types the answer
+    Vocabulary,
+    // numbers and arithmetic problems, typed with the digit keys
+    Arithmetic,
+    // every word starts with the last letter of the one before
+    WordChain,
+    // words are shown for a moment and then move on invisible
+    Memory,
+    // the same pack, seed and modifiers for everyone on the same day
+    Daily,
+    // handcrafted levels of scripted waves, rated with stars
+    Campaign
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 9] = [
+        GameMode::Classic,
+        GameMode::Adaptive,
+        GameMode::Review,
+        GameMode::Vocabulary,
+        GameMode::Arithmetic,
+        GameMode::WordChain,
+        GameMode::Memory,
+        GameMode::Daily,
+        GameMode::Campaign
+    ];
+
+    // used on the command line and in saved files
+    pub fn id(&self) -> &str {
+        match self {
+            GameMode::Classic => "classic",
+            GameMode::Adaptive => "adaptive",
+            GameMode::Review => "review",
+            GameMode::Vocabulary => "vocabulary",
+            GameMode::Arithmetic => "arithmetic",
+            GameMode::WordChain => "chain",
+            GameMode::Memory => "memory",
+            GameMode::Daily => "daily",
+            GameMode::Campaign => "campaign"
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        GameMode::ALL.iter().copied().find(|mode| mode.id() == id)
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            GameMode::Classic => "Classic",
+            GameMode::Adaptive => "Adaptive training",
+            GameMode::Review => "Review",
+            GameMode::Vocabulary => "Vocabulary",
+            GameMode::Arithmetic => "Arithmetic",
+            GameMode::WordChain => "Word chain",
+            GameMode::Memory => "Memory",
+            GameMode::Daily => "Daily challenge",
+            GameMode::Campaign => "Campaign"
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        match self {
+            GameMode::Classic => "Type the words before they escape",
+            GameMode::Adaptive => "More words with the keys you miss or type slowly",
+            GameMode::Review => "The words you missed come back until you know them",
+            GameMode::Vocabulary => "Type the answer to the word on the screen, for packs with word pairs",
+            GameMode::Arithmetic => "Type the numbers and the results, the digit keys don't use buffs",
+            GameMode::WordChain => "Type words starting with the last letter of the word before for a bonus",
+            GameMode::Memory => "Words vanish right after they appear, remember them",
+            GameMode::Daily => "Today's run, the first game of the day is ranked",
+            GameMode::Campaign => "Clear the levels one after the other and collect their stars"
+        }
+    }
+
+    // the digit keys type instead of using buffs
+    pub fn types_digits(&self) -> bool {
+        *self == GameMode::Arithmetic
+    }
+
+    // the buffs are bought and used with the digit keys
+    pub fn has_power_ups(&self) -> bool {
+        !self.types_digits()
+    }
+
+    // numbers aren't worth reviewing later
+    pub fn logs_mistakes(&self) -> bool {
+        *self != GameMode::Arithmetic
+    }
+
+    // scores which can't be compared with the normal game get a scoreboard of their own
+    pub fn has_own_scoreboard(&self) -> bool {
+        matches!(self, GameMode::Memory | GameMode::Campaign)
+    }
+
+    // boss sentences are plain words of the pack, which don't fit every mode
+    pub fn spawns_bosses(&self) -> bool {
+        !matches!(self, GameMode::Vocabulary | GameMode::Arithmetic | GameMode::Memory | GameMode::Campaign)
+    }
+
+    fn index(&self) -> usize {
+        GameMode::ALL.iter().position(|mode| mode == self).unwrap_or(0)
+    }
+
+    pub fn next(&self) -> Self {
+        GameMode::ALL[(self.index() + 1) % GameMode::ALL.len()]
+    }
+
+    pub fn previous(&self) -> Self {
+        GameMode::ALL[(self.index() + GameMode::ALL.len() - 1) % GameMode::ALL.len()]
+    }
+}
@@ -0,0 +1,374 @@
+use rand::Rng;
+
+use std::fmt::Debug;
+
+use crate::entities::Word;
+use crate::game_state::{ GameState, FogBand };
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Schedule {
+    // switched on for the rest of the game once the given seconds have passed
+    After(f32),
+    // switched on every `min .. max` seconds, the first time after `first` seconds
+    Random { first: f32, min: f32, max: f32 }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stacking {
+    // activating a running modifier restarts its timer
+    Refresh,
+    // up to the given number of activations run side by side
+    Stack(usize)
+}
+
+pub trait Modifier: Debug {
+    fn name(&self) -> &str;
+    fn schedule(&self) -> Schedule;
+
+    // seconds a single activation lasts, only used by `Schedule::Random`
+    fn duration(&self) -> f32 {
+        0.0
+    }
+
+    fn stacking(&self) -> Stacking {
+        Stacking::Refresh
+    }
+
+    fn on_start(&self, _state: &mut GameState) {}
+    fn on_end(&self, _state: &mut GameState) {}
+
+    // called for every spawned word while the modifier is active
+    fn on_word_spawned(&self, _word: &mut Word, _state: &mut GameState) {}
+}
+
+// words come faster with every spawn
+#[derive(Debug)]
+pub struct SpeedUp {
+    pub step: f32
+}
+
+impl SpeedUp {
+    pub const NAME: &'static str = "Speed-up";
+    pub const DEFAULT_STEP: f32 = 0.03;
+}
+
+impl Modifier for SpeedUp {
+    fn name(&self) -> &str {
+        SpeedUp::NAME
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::After(0.0)
+    }
+
+    fn on_word_spawned(&self, _word: &mut Word, state: &mut GameState) {
+        state.game_speed_up += self.step;
+    }
+}
+
+#[derive(Debug)]
+pub struct ColorChangingWords {
+    pub chance: f64
+}
+
+impl Modifier for ColorChangingWords {
+    fn name(&self) -> &str {
+        "Color changing words"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::After(0.0)
+    }
+
+    fn on_word_spawned(&self, word: &mut Word, state: &mut GameState) {
+        if state.rng.gen_bool(self.chance) {
+            word.is_color_changing = true;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ScreenShake;
+
+impl ScreenShake {
+    pub const DURATION: f32 = 1.0;
+}
+
+impl Modifier for ScreenShake {
+    fn name(&self) -> &str {
+        "Screen shake"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::Random { first: 10.0, min: 5.0, max: 20.0 }
+    }
+
+    fn duration(&self) -> f32 {
+        ScreenShake::DURATION
+    }
+
+    fn on_start(&self, state: &mut GameState) {
+        state.shake_screen = true;
+    }
+
+    fn on_end(&self, state: &mut GameState) {
+        state.shake_screen = false;
+    }
+}
+
+// words slowly disappear after they spawn
+#[derive(Debug)]
+pub struct FadingWords {
+    pub chance: f64,
+    pub fade_time: f32
+}
+
+impl Modifier for FadingWords {
+    fn name(&self) -> &str {
+        "Fading words"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::After(60.0)
+    }
+
+    fn on_word_spawned(&self, word: &mut Word, state: &mut GameState) {
+        if state.rng.gen_bool(self.chance) {
+            word.fade_time = Some(self.fade_time);
+        }
+    }
+}
+
+// words are shown backwards, but are typed as usual
+#[derive(Debug)]
+pub struct ReversedWords {
+    pub chance: f64
+}
+
+impl Modifier for ReversedWords {
+    fn name(&self) -> &str {
+        "Reversed words"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::After(30.0)
+    }
+
+    fn on_word_spawned(&self, word: &mut Word, state: &mut GameState) {
+        if state.rng.gen_bool(self.chance) {
+            word.is_reversed = true;
+        }
+    }
+}
+
+// letters are shuffled and fall into place while the word is typed
+#[derive(Debug)]
+pub struct ScrambledWords {
+    pub chance: f64
+}
+
+impl Modifier for ScrambledWords {
+    fn name(&self) -> &str {
+        "Scrambled words"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::After(45.0)
+    }
+
+    fn on_word_spawned(&self, word: &mut Word, state: &mut GameState) {
+        if state.rng.gen_bool(self.chance) {
+            word.scramble(&mut state.rng);
+        }
+    }
+}
+
+// a horizontal band hides the words passing through it
+#[derive(Debug)]
+pub struct Fog;
+
+impl Fog {
+    pub const DURATION: f32 = 6.0;
+    pub const BAND_HEIGHT: f32 = 0.2;
+    pub const MAX_BANDS: usize = 2;
+}
+
+impl Modifier for Fog {
+    fn name(&self) -> &str {
+        "Fog"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::Random { first: 40.0, min: 15.0, max: 30.0 }
+    }
+
+    fn duration(&self) -> f32 {
+        Fog::DURATION
+    }
+
+    fn stacking(&self) -> Stacking {
+        Stacking::Stack(Fog::MAX_BANDS)
+    }
+
+    fn on_start(&self, state: &mut GameState) {
+        let top = state.rng.gen_range(0.0 .. 1.0 - Fog::BAND_HEIGHT);
+        state.fog_bands.push(FogBand { top, height: Fog::BAND_HEIGHT });
+    }
+
+    fn on_end(&self, state: &mut GameState) {
+        // bands share the same duration, so the oldest one ends first
+        if !state.fog_bands.is_empty() {
+            state.fog_bands.remove(0);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RunningModifier {
+    index: usize,
+    remaining: f32
+}
+
+#[derive(Debug, Default)]
+pub struct ModifierRegistry {
+    modifiers: Vec<Box<dyn Modifier>>,
+    // seconds until the next activation of every `Schedule::Random` modifier
+    next_activation: Vec<f32>,
+    running: Vec<RunningModifier>,
+    elapsed: f32
+}
+
+impl ModifierRegistry {
+    pub fn new() -> Self {
+        ModifierRegistry {
+            modifiers: Vec::new(),
+            next_activation: Vec::new(),
+            running: Vec::new(),
+            elapsed: 0.0
+        }
+    }
+
+    // the nerfs of a normal game
+    pub fn with_defaults() -> Self {
+        let mut registry = ModifierRegistry::new();
+        registry.register(Box::new(SpeedUp { step: SpeedUp::DEFAULT_STEP }));
+        registry.register(Box::new(ColorChangingWords { chance: 0.3 }));
+        registry.register(Box::new(ScreenShake));
+        registry.register(Box::new(ReversedWords { chance: 0.15 }));
+        registry.register(Box::new(ScrambledWords { chance: 0.15 }));
+        registry.register(Box::new(FadingWords { chance: 0.15, fade_time: 2.5 }));
+        registry.register(Box::new(Fog));
+
+        registry
+    }
+
+    pub fn register(&mut self, modifier: Box<dyn Modifier>) {
+        let first = match modifier.schedule() {
+            Schedule::Random { first, .. } => first,
+            Schedule::After(_) => f32::INFINITY
+        };
+
+        self.modifiers.push(modifier);
+        self.next_activation.push(first);
+    }
+
+    // drops the modifiers with other names, before the game starts
+    pub fn keep(&mut self, names: &[String]) {
+        let (modifiers, next_activation) = std::mem::take(&mut self.modifiers).into_iter().
+            zip(std::mem::take(&mut self.next_activation)).
+            filter(|(modifier, _)| names.iter().any(|name| name == modifier.name())).
+            unzip();
+
+        self.modifiers = modifiers;
+        self.next_activation = next_activation;
+        self.running.clear();
+    }
+
+    // ends the running modifiers, before the registry is replaced
+    pub fn stop(&mut self, state: &mut GameState) {
+        for running in self.running.drain(..) {
+            self.modifiers[running.index].on_end(state);
+        }
+    }
+
+    // the modifiers switch on right away instead of waiting for their schedules
+    pub fn start_now(&mut self) {
+        self.elapsed = f32::INFINITY;
+        self.next_activation.iter_mut().for_each(|next_activation| *next_activation = 0.0);
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.modifiers.iter().map(|modifier| modifier.name().to_string()).collect()
+    }
+
+    pub fn modifiers(&self) -> &[Box<dyn Modifier>] {
+        &self.modifiers
+    }
+
+    pub fn running_count(&self, index: usize) -> usize {
+        self.running.iter().filter(|running| running.index == index).count()
+    }
+
+    pub fn is_active(&self, index: usize) -> bool {
+        match self.modifiers[index].schedule() {
+            Schedule::After(delay) => self.elapsed >= delay,
+            Schedule::Random { .. } => self.running_count(index) > 0
+        }
+    }
+
+    pub fn activate(&mut self, index: usize, state: &mut GameState) {
+        let modifier = &self.modifiers[index];
+        let duration = modifier.duration();
+
+        match modifier.stacking() {
+            Stacking::Refresh => {
+                match self.running.iter_mut().find(|running| running.index == index) {
+                    Some(running) => running.remaining = duration,
+                    None => {
+                        modifier.on_start(state);
+                        self.running.push(RunningModifier { index, remaining: duration });
+                    }
+                }
+            },
+            Stacking::Stack(max) => {
+                if self.running_count(index) < max {
+                    modifier.on_start(state);
+                    self.running.push(RunningModifier { index, remaining: duration });
+                }
+            }
+        }
+    }
+
+    pub fn update(&mut self, seconds: f32, state: &mut GameState) {
+        self.elapsed += seconds;
+
+        for running in self.running.iter_mut() {
+            running.remaining -= seconds;
+
+            if running.remaining <= 0.0 {
+                self.modifiers[running.index].on_end(state);
+            }
+        }
+
+        self.running.retain(|running| running.remaining > 0.0);
+
+        for index in 0 .. self.modifiers.len() {
+            if let Schedule::Random { min, max, .. } = self.modifiers[index].schedule() {
+                self.next_activation[index] -= seconds;
+
+                if self.next_activation[index] <= 0.0 {
+                    self.next_activation[index] = state.rng.gen_range(min .. max);
+                    self.activate(index, state);
+                }
+            }
+        }
+    }
+
+    pub fn on_word_spawned(&self, word: &mut Word, state: &mut GameState) {
+        for (index, modifier) in self.modifiers.iter().enumerate() {
+            if self.is_active(index) {
+                modifier.on_word_spawned(word, state);
+            }
+        }
+    }
+}
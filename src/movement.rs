@@ -0,0 +1,276 @@
+use ggez::graphics::Rect;
+use ggez::mint::Vector2;
+use rand::Rng;
+
+use std::f32::consts::PI;
+use std::fmt::Debug;
+
+pub trait Movement: Debug {
+    // displacement for the next `seconds`, `age` is the time the word has already been moving
+    fn step(&mut self, age: f32, seconds: f32) -> Vector2<f32>;
+    // the direction the word travels in overall
+    fn heading(&self) -> Heading;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Heading {
+    Right,
+    Left,
+    Down
+}
+
+impl Heading {
+    pub fn from_velocity(velocity: Vector2<f32>) -> Self {
+        if velocity.y.abs() > velocity.x.abs() {
+            return Heading::Down;
+        }
+
+        if velocity.x < 0.0 {
+            return Heading::Left;
+        }
+
+        Heading::Right
+    }
+
+    pub fn velocity(&self, speed: f32) -> Vector2<f32> {
+        match self {
+            Heading::Right => Vector2 { x: speed, y: 0.0 },
+            Heading::Left => Vector2 { x: -speed, y: 0.0 },
+            Heading::Down => Vector2 { x: 0.0, y: speed }
+        }
+    }
+
+    // a word escapes once it is completely past the side it travels to
+    pub fn has_escaped(&self, word_rect: Rect, field: Rect) -> bool {
+        match self {
+            Heading::Right => word_rect.x >= field.right(),
+            Heading::Left => word_rect.right() <= field.left(),
+            Heading::Down => word_rect.y >= field.bottom()
+        }
+    }
+
+    // how far the word still has to travel before it escapes
+    pub fn distance_to_escape(&self, word_rect: Rect, field: Rect) -> f32 {
+        match self {
+            Heading::Right => field.right() - word_rect.x,
+            Heading::Left => word_rect.right() - field.left(),
+            Heading::Down => field.bottom() - word_rect.y
+        }
+    }
+}
+
+fn scale(vector: Vector2<f32>, factor: f32) -> Vector2<f32> {
+    Vector2 { x: vector.x * factor, y: vector.y * factor }
+}
+
+fn add(first: Vector2<f32>, second: Vector2<f32>) -> Vector2<f32> {
+    Vector2 { x: first.x + second.x, y: first.y + second.y }
+}
+
+// unit vector turned 90 degrees from the velocity, used for side to side offsets
+pub fn perpendicular(velocity: Vector2<f32>) -> Vector2<f32> {
+    let length = (velocity.x * velocity.x + velocity.y * velocity.y).sqrt();
+
+    if length == 0.0 {
+        return Vector2 { x: 0.0, y: 1.0 };
+    }
+
+    Vector2 { x: -velocity.y / length, y: velocity.x / length }
+}
+
+#[derive(Debug)]
+pub struct Linear {
+    pub velocity: Vector2<f32>
+}
+
+impl Movement for Linear {
+    fn step(&mut self, _age: f32, seconds: f32) -> Vector2<f32> {
+        scale(self.velocity, seconds)
+    }
+
+    fn heading(&self) -> Heading {
+        Heading::from_velocity(self.velocity)
+    }
+}
+
+// drifts side to side along a sine wave
+#[derive(Debug)]
+pub struct Sinusoidal {
+    pub velocity: Vector2<f32>,
+    pub amplitude: f32,
+    // waves per second
+    pub frequency: f32
+}
+
+impl Sinusoidal {
+    fn offset(&self, time: f32) -> f32 {
+        self.amplitude * (2.0 * PI * self.frequency * time).sin()
+    }
+}
+
+impl Movement for Sinusoidal {
+    fn step(&mut self, age: f32, seconds: f32) -> Vector2<f32> {
+        let drift = self.offset(age + seconds) - self.offset(age);
+        add(scale(self.velocity, seconds), scale(perpendicular(self.velocity), drift))
+    }
+
+    fn heading(&self) -> Heading {
+        Heading::from_velocity(self.velocity)
+    }
+}
+
+// like the sine wave, but with sharp turns
+#[derive(Debug)]
+pub struct ZigZag {
+    pub velocity: Vector2<f32>,
+    pub amplitude: f32,
+    // seconds for a full zig and zag
+    pub period: f32
+}
+
+impl ZigZag {
+    fn offset(&self, time: f32) -> f32 {
+        if self.period <= 0.0 {
+            return 0.0;
+        }
+
+        let phase = (time / self.period).fract();
+        let triangle = if phase < 0.5 { phase * 4.0 - 1.0 } else { 3.0 - phase * 4.0 };
+
+        self.amplitude * triangle
+    }
+}
+
+impl Movement for ZigZag {
+    fn step(&mut self, age: f32, seconds: f32) -> Vector2<f32> {
+        let drift = self.offset(age + seconds) - self.offset(age);
+        add(scale(self.velocity, seconds), scale(perpendicular(self.velocity), drift))
+    }
+
+    fn heading(&self) -> Heading {
+        Heading::from_velocity(self.velocity)
+    }
+}
+
+// starts slow and keeps getting faster
+#[derive(Debug)]
+pub struct Accelerating {
+    pub velocity: Vector2<f32>,
+    // added to the speed every second, as a fraction of the starting speed
+    pub acceleration: f32
+}
+
+impl Movement for Accelerating {
+    fn step(&mut self, age: f32, seconds: f32) -> Vector2<f32> {
+        // distance under constant acceleration: v * t + a * t^2 / 2
+        let distance = |time: f32| time + self.acceleration * time * time / 2.0;
+        scale(self.velocity, distance(age + seconds) - distance(age))
+    }
+
+    fn heading(&self) -> Heading {
+        Heading::from_velocity(self.velocity)
+    }
+}
+
+// bends away from a straight line along a parabola
+#[derive(Debug)]
+pub struct Curved {
+    pub velocity: Vector2<f32>,
+    // sideways acceleration in pixels per second squared
+    pub bend: f32
+}
+
+impl Movement for Curved {
+    fn step(&mut self, age: f32, seconds: f32) -> Vector2<f32> {
+        let offset = |time: f32| self.bend * time * time / 2.0;
+        add(scale(self.velocity, seconds), scale(perpendicular(self.velocity), offset(age + seconds) - offset(age)))
+    }
+
+    fn heading(&self) -> Heading {
+        Heading::from_velocity(self.velocity)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pattern {
+    Linear,
+    Sinusoidal,
+    ZigZag,
+    Accelerating,
+    Curved
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpawnSide {
+    Left,
+    Right,
+    Top
+}
+
+impl SpawnSide {
+    pub fn velocity(&self, speed: f32) -> Vector2<f32> {
+        match self {
+            SpawnSide::Left => Vector2 { x: speed, y: 0.0 },
+            SpawnSide::Right => Vector2 { x: -speed, y: 0.0 },
+            SpawnSide::Top => Vector2 { x: 0.0, y: speed }
+        }
+    }
+}
+
+// difficulty at which each pattern starts to show up, the game speed-up is used as difficulty
+const PATTERN_UNLOCKS: [(Pattern, f32); 5] = [
+    (Pattern::Linear, 0.0),
+    (Pattern::Sinusoidal, 0.3),
+    (Pattern::ZigZag, 0.6),
+    (Pattern::Accelerating, 0.9),
+    (Pattern::Curved, 1.2)
+];
+
+const SIDE_UNLOCKS: [(SpawnSide, f32); 3] = [
+    (SpawnSide::Left, 0.0),
+    (SpawnSide::Right, 1.0),
+    (SpawnSide::Top, 1.5)
+];
+
+// the left to right straight line stays the most common choice
+const STRAIGHT_WEIGHT: usize = 2;
+
+pub fn pick_pattern<R: Rng>(difficulty: f32, rng: &mut R) -> Pattern {
+    let unlocked = PATTERN_UNLOCKS.iter().filter(|(_, unlock)| difficulty >= *unlock).count();
+    let roll = rng.gen_range(0 .. unlocked + STRAIGHT_WEIGHT);
+
+    PATTERN_UNLOCKS[roll.saturating_sub(STRAIGHT_WEIGHT)].0
+}
+
+pub fn pick_side<R: Rng>(difficulty: f32, rng: &mut R) -> SpawnSide {
+    let unlocked = SIDE_UNLOCKS.iter().filter(|(_, unlock)| difficulty >= *unlock).count();
+    let roll = rng.gen_range(0 .. unlocked + STRAIGHT_WEIGHT);
+
+    SIDE_UNLOCKS[roll.saturating_sub(STRAIGHT_WEIGHT)].0
+}
+
+// `toward_center` is positive when the field center lies on the `perpendicular` side of the word,
+// curved words bend that way so they don't leave the field
+pub fn build_movement<R: Rng>(pattern: Pattern, velocity: Vector2<f32>, toward_center: f32, rng: &mut R) -> Box<dyn Movement> {
+    match pattern {
+        Pattern::Linear => Box::new(Linear { velocity }),
+        Pattern::Sinusoidal => Box::new(Sinusoidal {
+            velocity,
+            amplitude: rng.gen_range(20.0 .. 60.0),
+            frequency: rng.gen_range(0.2 .. 0.6)
+        }),
+        Pattern::ZigZag => Box::new(ZigZag {
+            velocity,
+            amplitude: rng.gen_range(20.0 .. 60.0),
+            period: rng.gen_range(1.0 .. 2.5)
+        }),
+        Pattern::Accelerating => Box::new(Accelerating {
+            velocity: scale(velocity, 0.5),
+            acceleration: rng.gen_range(0.2 .. 0.5)
+        }),
+        Pattern::Curved => Box::new(Curved {
+            velocity,
+            bend: rng.gen_range(5.0 .. 15.0) * toward_center.signum()
+        })
+    }
+}
@@ -0,0 +1,521 @@
+use ggez::event::KeyCode;
+use rand::seq;
+
+use std::fmt::Debug;
+
+use crate::game_state::GameState;
+use crate::scoring::KillCause;
+
+pub trait PowerUp: Debug {
+    // text shown in the top panel, e.g. "extra life"
+    fn hud_label(&self) -> String;
+    // text shown in the info panel, e.g. "for extra life"
+    fn description(&self) -> String;
+    // price of the first purchase in a run, it grows with every purchase
+    fn cost(&self) -> f32;
+    // seconds to wait after using the buff before it can be used again
+    fn cooldown(&self) -> f32;
+    // shown in the panels as "(1)"
+    fn key_label(&self) -> &str;
+    fn hotkeys(&self) -> &[KeyCode];
+
+    // seconds the effect lasts, `None` for instant buffs
+    fn duration(&self) -> Option<f32> {
+        None
+    }
+
+    // extra rule on top of having enough cash
+    fn is_available(&self, _state: &GameState) -> bool {
+        true
+    }
+
+    fn apply(&self, state: &mut GameState);
+
+    // called once the duration runs out
+    fn expire(&self, _state: &mut GameState) {}
+
+    // lets a timed buff end before its duration, e.g. a shield that took a hit
+    fn is_spent(&self, _state: &GameState) -> bool {
+        false
+    }
+}
+
+#[derive(Debug)]
+pub struct ExtraLife;
+
+impl ExtraLife {
+    pub const COST: f32 = 300.0;
+    pub const COOLDOWN: f32 = 30.0;
+}
+
+impl PowerUp for ExtraLife {
+    fn hud_label(&self) -> String {
+        String::from("extra life")
+    }
+
+    fn description(&self) -> String {
+        String::from("for extra life")
+    }
+
+    fn cost(&self) -> f32 {
+        ExtraLife::COST
+    }
+
+    fn cooldown(&self) -> f32 {
+        ExtraLife::COOLDOWN
+    }
+
+    fn key_label(&self) -> &str {
+        "1"
+    }
+
+    fn hotkeys(&self) -> &[KeyCode] {
+        &[KeyCode::Key1, KeyCode::Numpad1]
+    }
+
+    fn apply(&self, state: &mut GameState) {
+        state.remaining_lifes += 1;
+    }
+}
+
+#[derive(Debug)]
+pub struct RemoveWords {
+    pub count: usize
+}
+
+impl RemoveWords {
+    pub const COST: f32 = 350.0;
+    pub const COOLDOWN: f32 = 8.0;
+    pub const DEFAULT_COUNT: usize = 2;
+}
+
+impl PowerUp for RemoveWords {
+    fn hud_label(&self) -> String {
+        format!("Remove {} words", self.count)
+    }
+
+    fn description(&self) -> String {
+        String::from("for words removal")
+    }
+
+    fn cost(&self) -> f32 {
+        RemoveWords::COST
+    }
+
+    fn cooldown(&self) -> f32 {
+        RemoveWords::COOLDOWN
+    }
+
+    fn key_label(&self) -> &str {
+        "2"
+    }
+
+    fn hotkeys(&self) -> &[KeyCode] {
+        &[KeyCode::Key2, KeyCode::Numpad2]
+    }
+
+    fn is_available(&self, state: &GameState) -> bool {
+        !state.words.is_empty()
+    }
+
+    fn apply(&self, state: &mut GameState) {
+        if state.words.len() <= self.count {
+            for index in 0 .. state.words.len() {
+                state.kill_word(index, KillCause::PowerUp);
+            }
+        }
+        else {
+            let sample_indexes = seq::index::sample(&mut state.rng, state.words.len(), self.count);
+
+            for index in sample_indexes.iter() {
+                state.kill_word(index, KillCause::PowerUp);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SlowWordSpawn;
+
+impl SlowWordSpawn {
+    pub const COST: f32 = 1000.0;
+    pub const COOLDOWN: f32 = 45.0;
+}
+
+impl PowerUp for SlowWordSpawn {
+    fn hud_label(&self) -> String {
+        String::from("Slow spawn")
+    }
+
+    fn description(&self) -> String {
+        String::from("for slow words spawn")
+    }
+
+    fn cost(&self) -> f32 {
+        SlowWordSpawn::COST
+    }
+
+    fn cooldown(&self) -> f32 {
+        SlowWordSpawn::COOLDOWN
+    }
+
+    fn key_label(&self) -> &str {
+        "3"
+    }
+
+    fn hotkeys(&self) -> &[KeyCode] {
+        &[KeyCode::Key3, KeyCode::Numpad3]
+    }
+
+    fn apply(&self, state: &mut GameState) {
+        state.game_speed_up /= 2.0;
+    }
+}
+
+#[derive(Debug)]
+pub struct TimeFreeze;
+
+impl TimeFreeze {
+    pub const COST: f32 = 600.0;
+    pub const COOLDOWN: f32 = 20.0;
+    pub const DURATION: f32 = 3.0;
+}
+
+impl PowerUp for TimeFreeze {
+    fn hud_label(&self) -> String {
+        String::from("Freeze")
+    }
+
+    fn description(&self) -> String {
+        format!("to freeze the words for {:.0}s", TimeFreeze::DURATION)
+    }
+
+    fn cost(&self) -> f32 {
+        TimeFreeze::COST
+    }
+
+    fn cooldown(&self) -> f32 {
+        TimeFreeze::COOLDOWN
+    }
+
+    fn key_label(&self) -> &str {
+        "4"
+    }
+
+    fn hotkeys(&self) -> &[KeyCode] {
+        &[KeyCode::Key4, KeyCode::Numpad4]
+    }
+
+    fn duration(&self) -> Option<f32> {
+        Some(TimeFreeze::DURATION)
+    }
+
+    fn apply(&self, state: &mut GameState) {
+        state.time_frozen = true;
+    }
+
+    fn expire(&self, state: &mut GameState) {
+        state.time_frozen = false;
+    }
+}
+
+#[derive(Debug)]
+pub struct Shield;
+
+impl Shield {
+    pub const COST: f32 = 400.0;
+    pub const COOLDOWN: f32 = 25.0;
+    pub const DURATION: f32 = 20.0;
+}
+
+impl PowerUp for Shield {
+    fn hud_label(&self) -> String {
+        String::from("Shield")
+    }
+
+    fn description(&self) -> String {
+        String::from("for a shield against the next escaped word")
+    }
+
+    fn cost(&self) -> f32 {
+        Shield::COST
+    }
+
+    fn cooldown(&self) -> f32 {
+        Shield::COOLDOWN
+    }
+
+    fn key_label(&self) -> &str {
+        "5"
+    }
+
+    fn hotkeys(&self) -> &[KeyCode] {
+        &[KeyCode::Key5, KeyCode::Numpad5]
+    }
+
+    fn duration(&self) -> Option<f32> {
+        Some(Shield::DURATION)
+    }
+
+    fn apply(&self, state: &mut GameState) {
+        state.shield_active = true;
+    }
+
+    fn expire(&self, state: &mut GameState) {
+        state.shield_active = false;
+    }
+
+    fn is_spent(&self, state: &GameState) -> bool {
+        !state.shield_active
+    }
+}
+
+#[derive(Debug)]
+pub struct SlowMotion;
+
+impl SlowMotion {
+    pub const COST: f32 = 500.0;
+    pub const COOLDOWN: f32 = 20.0;
+    pub const DURATION: f32 = 6.0;
+}
+
+impl PowerUp for SlowMotion {
+    fn hud_label(&self) -> String {
+        String::from("Slow motion")
+    }
+
+    fn description(&self) -> String {
+        format!("for half speed words for {:.0}s", SlowMotion::DURATION)
+    }
+
+    fn cost(&self) -> f32 {
+        SlowMotion::COST
+    }
+
+    fn cooldown(&self) -> f32 {
+        SlowMotion::COOLDOWN
+    }
+
+    fn key_label(&self) -> &str {
+        "6"
+    }
+
+    fn hotkeys(&self) -> &[KeyCode] {
+        &[KeyCode::Key6, KeyCode::Numpad6]
+    }
+
+    fn duration(&self) -> Option<f32> {
+        Some(SlowMotion::DURATION)
+    }
+
+    fn apply(&self, state: &mut GameState) {
+        state.slow_motion = true;
+    }
+
+    fn expire(&self, state: &mut GameState) {
+        state.slow_motion = false;
+    }
+}
+
+// the bomb goes off when its fuse burns out
+#[derive(Debug)]
+pub struct Bomb {
+    pub column: f32
+}
+
+impl Bomb {
+    pub const COST: f32 = 800.0;
+    pub const COOLDOWN: f32 = 15.0;
+    pub const FUSE: f32 = 1.5;
+    pub const DEFAULT_COLUMN: f32 = 400.0;
+}
+
+impl PowerUp for Bomb {
+    fn hud_label(&self) -> String {
+        String::from("Bomb")
+    }
+
+    fn description(&self) -> String {
+        String::from("for a bomb on the left side")
+    }
+
+    fn cost(&self) -> f32 {
+        Bomb::COST
+    }
+
+    fn cooldown(&self) -> f32 {
+        Bomb::COOLDOWN
+    }
+
+    fn key_label(&self) -> &str {
+        "7"
+    }
+
+    fn hotkeys(&self) -> &[KeyCode] {
+        &[KeyCode::Key7, KeyCode::Numpad7]
+    }
+
+    fn duration(&self) -> Option<f32> {
+        Some(Bomb::FUSE)
+    }
+
+    fn apply(&self, _state: &mut GameState) {}
+
+    fn expire(&self, state: &mut GameState) {
+        for index in 0 .. state.words.len() {
+            if !state.words[index].is_typed && state.words[index].pos.x < self.column {
+                state.kill_word(index, KillCause::PowerUp);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ActivePowerUp {
+    index: usize,
+    remaining: f32
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct InventorySlot {
+    owned: u32,
+    times_bought: u32,
+    cooldown_left: f32
+}
+
+#[derive(Debug, Default)]
+pub struct PowerUpRegistry {
+    power_ups: Vec<Box<dyn PowerUp>>,
+    inventory: Vec<InventorySlot>,
+    active: Vec<ActivePowerUp>
+}
+
+impl PowerUpRegistry {
+    // every purchase makes the next one of the same buff this much more expensive
+    pub const PRICE_GROWTH: f32 = 1.25;
+
+    pub fn new() -> Self {
+        PowerUpRegistry {
+            power_ups: Vec::new(),
+            inventory: Vec::new(),
+            active: Vec::new()
+        }
+    }
+
+    // the buffs available in a normal game
+    pub fn with_defaults() -> Self {
+        let mut registry = PowerUpRegistry::new();
+        registry.register(Box::new(ExtraLife));
+        registry.register(Box::new(RemoveWords { count: RemoveWords::DEFAULT_COUNT }));
+        registry.register(Box::new(SlowWordSpawn));
+        registry.register(Box::new(TimeFreeze));
+        registry.register(Box::new(Shield));
+        registry.register(Box::new(SlowMotion));
+        registry.register(Box::new(Bomb { column: Bomb::DEFAULT_COLUMN }));
+
+        registry
+    }
+
+    pub fn register(&mut self, power_up: Box<dyn PowerUp>) {
+        self.power_ups.push(power_up);
+        self.inventory.push(InventorySlot::default());
+    }
+
+    pub fn power_ups(&self) -> &[Box<dyn PowerUp>] {
+        &self.power_ups
+    }
+
+    pub fn find_by_key(&self, keycode: KeyCode) -> Option<usize> {
+        self.power_ups.iter().position(|power_up| power_up.hotkeys().contains(&keycode))
+    }
+
+    pub fn price(&self, index: usize) -> f32 {
+        let times_bought = self.inventory[index].times_bought as i32;
+        self.power_ups[index].cost() * PowerUpRegistry::PRICE_GROWTH.powi(times_bought)
+    }
+
+    pub fn can_afford(&self, index: usize, state: &GameState) -> bool {
+        state.cash >= self.price(index)
+    }
+
+    pub fn owned(&self, index: usize) -> u32 {
+        self.inventory[index].owned
+    }
+
+    // 1.0 right after use, 0.0 when the buff is ready again
+    pub fn cooldown_progress(&self, index: usize) -> f32 {
+        let cooldown = self.power_ups[index].cooldown();
+
+        if cooldown <= 0.0 {
+            return 0.0;
+        }
+
+        self.inventory[index].cooldown_left / cooldown
+    }
+
+    // puts the buff in the inventory, returns true when it was bought
+    pub fn buy(&mut self, index: usize, state: &mut GameState) -> bool {
+        if !self.can_afford(index, state) {
+            return false;
+        }
+
+        state.cash -= self.price(index);
+        self.inventory[index].owned += 1;
+        self.inventory[index].times_bought += 1;
+
+        true
+    }
+
+    // uses an owned buff, returns true when it was triggered
+    pub fn activate(&mut self, index: usize, state: &mut GameState) -> bool {
+        let power_up = &self.power_ups[index];
+        let slot = &mut self.inventory[index];
+
+        if slot.owned == 0 || slot.cooldown_left > 0.0 || !power_up.is_available(state) {
+            return false;
+        }
+
+        slot.owned -= 1;
+        slot.cooldown_left = power_up.cooldown();
+        power_up.apply(state);
+
+        if let Some(duration) = power_up.duration() {
+            // using an already active buff restarts its timer
+            match self.active.iter_mut().find(|active| active.index == index) {
+                Some(active) => active.remaining = duration,
+                None => self.active.push(ActivePowerUp { index, remaining: duration })
+            }
+        }
+
+        true
+    }
+
+    pub fn remaining_time(&self, index: usize) -> Option<f32> {
+        self.active.iter().find(|active| active.index == index).map(|active| active.remaining)
+    }
+
+    // (index, seconds left) of every running timed buff
+    pub fn active_timers(&self) -> Vec<(usize, f32)> {
+        self.active.iter().map(|active| (active.index, active.remaining)).collect()
+    }
+
+    pub fn update(&mut self, seconds: f32, state: &mut GameState) {
+        for slot in self.inventory.iter_mut() {
+            slot.cooldown_left = (slot.cooldown_left - seconds).max(0.0);
+        }
+
+        for active in self.active.iter_mut() {
+            let power_up = &self.power_ups[active.index];
+            active.remaining -= seconds;
+
+            if power_up.is_spent(state) {
+                active.remaining = 0.0;
+            }
+            else if active.remaining <= 0.0 {
+                power_up.expire(state);
+            }
+        }
+
+        self.active.retain(|active| active.remaining > 0.0);
+    }
+}
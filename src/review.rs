@@ -0,0 +1,179 @@
+use rand::Rng;
+use rand::rngs::StdRng;
+
+use std::collections::BTreeMap;
+use std::time::{ SystemTime, UNIX_EPOCH };
+
+use crate::entities::{ Word, WordKind };
+use crate::word_sources::{ self, DictionarySource, WordSource };
+
+// seconds a word waits in each box before it's due again, a word typed in the last box is mastered
+pub const INTERVALS: [u64; 5] = [0, 10 * 60, 24 * 60 * 60, 3 * 24 * 60 * 60, 7 * 24 * 60 * 60];
+pub const MASTERED_BOX: usize = INTERVALS.len();
+
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0)
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReviewEntry {
+    // a miss puts the word back into the first box
+    pub box_index: usize,
+    // unix time in seconds
+    pub due: u64,
+    pub misses: u32,
+    pub reviews: u32
+}
+
+impl ReviewEntry {
+    pub fn is_mastered(&self) -> bool {
+        self.box_index >= MASTERED_BOX
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        !self.is_mastered() && self.due <= now
+    }
+}
+
+// words which escaped or were mistyped, scheduled like flash cards in boxes
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MistakeLog {
+    pub entries: BTreeMap<String, ReviewEntry>
+}
+
+impl MistakeLog {
+    pub const CSV_HEADER: &'static str = "word,box,due,misses,reviews,mastered";
+
+    pub fn record_miss(&mut self, word: &str, now: u64) {
+        let entry = self.entries.entry(word.to_string()).or_default();
+        entry.box_index = 0;
+        entry.due = now;
+        entry.misses += 1;
+    }
+
+    // words of the pack or already in the log, not split halves, generated words or boss sentences
+    pub fn tracks(&self, word: &Word, pack_words: &[String]) -> bool {
+        let label = word.label();
+
+        !matches!(word.kind, WordKind::Boss { .. }) &&
+        (self.entries.contains_key(label) || pack_words.iter().any(|pack_word| pack_word == label))
+    }
+
+    // only words of the log which are due move to the next box
+    pub fn record_success(&mut self, word: &str, now: u64) {
+        let entry = match self.entries.get_mut(word) {
+            Some(entry) if entry.is_due(now) => entry,
+            _ => return
+        };
+
+        entry.reviews += 1;
+        entry.box_index += 1;
+        entry.due = now + INTERVALS.get(entry.box_index).copied().unwrap_or(0);
+    }
+
+    // the words waiting the longest come first
+    pub fn due_words(&self, now: u64) -> Vec<String> {
+        let mut due = self.entries.iter().filter(|(_, entry)| entry.is_due(now)).collect::<Vec<(&String, &ReviewEntry)>>();
+        due.sort_by_key(|(word, entry)| (entry.due, *word));
+
+        due.into_iter().map(|(word, _)| word.clone()).collect()
+    }
+
+    // `word<TAB>box<TAB>due<TAB>misses<TAB>reviews` lines
+    pub fn serialize(&self) -> String {
+        self.entries.iter().
+            map(|(word, entry)| format!("{}\t{}\t{}\t{}\t{}\n", word, entry.box_index, entry.due, entry.misses, entry.reviews)).
+            collect()
+    }
+
+    // broken lines are skipped
+    pub fn parse(text: &str) -> Self {
+        let mut log = MistakeLog::default();
+
+        for line in text.lines() {
+            let fields = line.split('\t').collect::<Vec<&str>>();
+
+            if fields.len() != 5 || fields[0].is_empty() {
+                continue;
+            }
+
+            if let (Ok(box_index), Ok(due), Ok(misses), Ok(reviews)) = (fields[1].parse(), fields[2].parse(), fields[3].parse(), fields[4].parse()) {
+                log.entries.insert(fields[0].to_string(), ReviewEntry { box_index, due, misses, reviews });
+            }
+        }
+
+        log
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{}\n", MistakeLog::CSV_HEADER);
+
+        for (word, entry) in &self.entries {
+            csv.push_str(&format!("{},{},{},{},{},{}\n", csv_field(word), entry.box_index, entry.due, entry.misses, entry.reviews, entry.is_mastered()));
+        }
+
+        csv
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    }
+    else {
+        field.to_string()
+    }
+}
+
+// due words most of the time, the rest comes from the dictionary
+#[derive(Debug)]
+pub struct ReviewSource {
+    due: Vec<String>,
+    dictionary: DictionarySource,
+    rng: StdRng
+}
+
+impl ReviewSource {
+    pub const DUE_CHANCE: f64 = 0.6;
+
+    pub fn new(due: Vec<String>, words: Vec<String>, seed: Option<u64>) -> Self {
+        ReviewSource {
+            due,
+            dictionary: DictionarySource::new(words, seed),
+            rng: word_sources::seeded_rng(seed)
+        }
+    }
+
+    pub fn due(&self) -> &[String] {
+        &self.due
+    }
+}
+
+impl WordSource for ReviewSource {
+    fn name(&self) -> &str {
+        "Review"
+    }
+
+    fn next_word(&mut self) -> Option<String> {
+        if !self.due.is_empty() && self.rng.gen_bool(ReviewSource::DUE_CHANCE) {
+            let index = self.rng.gen_range(0 .. self.due.len());
+            return Some(self.due[index].clone());
+        }
+
+        self.dictionary.next_word().or_else(|| self.due.first().cloned())
+    }
+
+    fn set_difficulty(&mut self, level: f32) {
+        self.dictionary.set_difficulty(level);
+    }
+
+    // a typed word is done for this game, a missed one comes back
+    fn word_result(&mut self, word: &str, typed: bool) {
+        if typed {
+            self.due.retain(|due| due != word);
+        }
+        else if !self.due.iter().any(|due| due == word) {
+            self.due.push(word.to_string());
+        }
+    }
+}
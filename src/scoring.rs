@@ -0,0 +1,143 @@
+use std::fmt::Debug;
+
+use crate::entities::Word;
+
+// consecutive correctly typed words
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Combo {
+    pub streak: u32
+}
+
+impl Combo {
+    pub const STEP: f32 = 0.1;
+    pub const MAX_MULTIPLIER: f32 = 3.0;
+
+    pub fn multiplier(&self) -> f32 {
+        (1.0 + self.streak as f32 * Combo::STEP).min(Combo::MAX_MULTIPLIER)
+    }
+
+    pub fn hit(&mut self) {
+        self.streak += 1;
+    }
+
+    // a missed word or a typo breaks the streak
+    pub fn reset(&mut self) {
+        self.streak = 0;
+    }
+}
+
+pub trait ScoringRule: Debug {
+    // points for a typed word, the combo already includes the word itself
+    fn score(&self, word: &Word, combo: &Combo) -> f32;
+    // one line for the info panel
+    fn describe(&self) -> String;
+}
+
+// letters that show up rarely in english words and are awkward to reach
+pub const RARE_LETTERS: &str = "jqxzkvwy";
+
+pub fn rare_letters_count(label: &str) -> usize {
+    label.chars().filter(|letter| RARE_LETTERS.contains(letter.to_ascii_lowercase())).count()
+}
+
+// the word difficulty is already part of the base reward, so only the combo is added here
+#[derive(Debug, Default)]
+pub struct ComboScoring;
+
+impl ScoringRule for ComboScoring {
+    fn score(&self, word: &Word, combo: &Combo) -> f32 {
+        word.get_reward() * combo.multiplier()
+    }
+
+    fn describe(&self) -> String {
+        format!("speed x difficulty, x{:.0} for color changing words, up to x{:.0} for a combo",
+                Word::COLOR_CHANGING_MULTIPLIER,
+                Combo::MAX_MULTIPLIER)
+    }
+}
+
+#[derive(Debug)]
+pub struct CashScoring {
+    // part of the combo bonus which is paid out as cash
+    pub combo_share: f32
+}
+
+impl Default for CashScoring {
+    fn default() -> Self {
+        CashScoring {
+            combo_share: 0.5
+        }
+    }
+}
+
+impl ScoringRule for CashScoring {
+    fn score(&self, word: &Word, combo: &Combo) -> f32 {
+        let combo_bonus = (combo.multiplier() - 1.0) * self.combo_share;
+        word.get_reward() * word.cash_multiplier() * (1.0 + combo_bonus)
+    }
+
+    fn describe(&self) -> String {
+        format!("speed x difficulty, {:.0}% of the combo bonus", self.combo_share * 100.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KillCause {
+    Typed,
+    // removed by a buff, like the bomb
+    PowerUp
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Reward {
+    pub score: f32,
+    pub cash: f32
+}
+
+// score goes to the scoreboard, cash pays for the buffs
+#[derive(Debug)]
+pub struct Economy {
+    pub score_rule: Box<dyn ScoringRule>,
+    pub cash_rule: Box<dyn ScoringRule>,
+    // part of the normal reward paid for words removed by buffs, without any combo
+    pub power_up_score_share: f32,
+    pub power_up_cash_share: f32
+}
+
+impl Economy {
+    pub fn reward(&self, word: &Word, combo: &Combo, cause: KillCause) -> Reward {
+        match cause {
+            KillCause::Typed => Reward {
+                score: self.score_rule.score(word, combo),
+                cash: self.cash_rule.score(word, combo)
+            },
+            KillCause::PowerUp => {
+                let no_combo = Combo::default();
+
+                Reward {
+                    score: self.score_rule.score(word, &no_combo) * self.power_up_score_share,
+                    cash: self.cash_rule.score(word, &no_combo) * self.power_up_cash_share
+                }
+            }
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        format!("Score: {}\nCash: {}\nWords removed by buffs: {:.0}% score, {:.0}% cash",
+                self.score_rule.describe(),
+                self.cash_rule.describe(),
+                self.power_up_score_share * 100.0,
+                self.power_up_cash_share * 100.0)
+    }
+}
+
+impl Default for Economy {
+    fn default() -> Self {
+        Economy {
+            score_rule: Box::new(ComboScoring),
+            cash_rule: Box::new(CashScoring::default()),
+            power_up_score_share: 0.0,
+            power_up_cash_share: 0.25
+        }
+    }
+}
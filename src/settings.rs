@@ -0,0 +1,39 @@
+// options the player changes in the menu, saved between games
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    // hides the blocklisted words and the words with an excluded tag
+    pub content_filter: bool,
+    pub excluded_tags: Vec<String>
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            content_filter: false,
+            excluded_tags: vec![String::from("mature"), String::from("violence"), String::from("alcohol")]
+        }
+    }
+}
+
+impl Settings {
+    // `key: value` lines like the pack manifests, missing or broken values keep their defaults
+    pub fn parse(text: &str) -> Self {
+        let mut settings = Settings::default();
+
+        for (key, value) in text.lines().filter_map(|line| line.split_once(':')) {
+            let value = value.trim();
+
+            match key.trim() {
+                "content_filter" => settings.content_filter = value.parse().unwrap_or(settings.content_filter),
+                "excluded_tags" => settings.excluded_tags = value.split(',').map(|tag| tag.trim().to_string()).filter(|tag| !tag.is_empty()).collect(),
+                _ => ()
+            }
+        }
+
+        settings
+    }
+
+    pub fn serialize(&self) -> String {
+        format!("content_filter: {}\nexcluded_tags: {}", self.content_filter, self.excluded_tags.join(", "))
+    }
+}
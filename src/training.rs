@@ -0,0 +1,257 @@
+use rand::Rng;
+use rand::distributions::WeightedIndex;
+use rand::rngs::StdRng;
+
+use std::collections::BTreeMap;
+
+use crate::word_sources::{ self, WordSource };
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct KeyStats {
+    pub presses: u32,
+    pub errors: u32,
+    // seconds since the previous key, summed over the presses which had one
+    pub total_latency: f32,
+    pub timed_presses: u32
+}
+
+impl KeyStats {
+    pub fn error_rate(&self) -> f32 {
+        if self.presses == 0 { 0.0 } else { self.errors as f32 / self.presses as f32 }
+    }
+
+    pub fn mean_latency(&self) -> Option<f32> {
+        if self.timed_presses == 0 { None } else { Some(self.total_latency / self.timed_presses as f32) }
+    }
+
+    fn record(&mut self, correct: bool, latency: Option<f32>) {
+        self.presses += 1;
+
+        if !correct {
+            self.errors += 1;
+        }
+
+        if let Some(latency) = latency {
+            self.total_latency += latency;
+            self.timed_presses += 1;
+        }
+    }
+
+    fn add(&mut self, other: &KeyStats) {
+        self.presses += other.presses;
+        self.errors += other.errors;
+        self.total_latency += other.total_latency;
+        self.timed_presses += other.timed_presses;
+    }
+}
+
+// errors and latency of every key and every pair of keys
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TypingStats {
+    pub keys: BTreeMap<char, KeyStats>,
+    pub bigrams: BTreeMap<String, KeyStats>
+}
+
+impl TypingStats {
+    // keys with fewer presses don't say much about the player
+    pub const MIN_PRESSES: u32 = 5;
+    // how much slower than the player's average key counts as much as always missing it
+    pub const SLOWNESS_WEIGHT: f32 = 0.5;
+
+    // `previous` is the key typed right before, if it belongs to the same word
+    pub fn record(&mut self, previous: Option<char>, letter: char, correct: bool, latency: Option<f32>) {
+        let letter = letter.to_ascii_lowercase();
+        self.keys.entry(letter).or_default().record(correct, latency);
+
+        if let Some(previous) = previous {
+            let bigram = format!("{}{}", previous.to_ascii_lowercase(), letter);
+            self.bigrams.entry(bigram).or_default().record(correct, latency);
+        }
+    }
+
+    pub fn merge(&mut self, other: &TypingStats) {
+        for (key, stats) in &other.keys {
+            self.keys.entry(*key).or_default().add(stats);
+        }
+
+        for (bigram, stats) in &other.bigrams {
+            self.bigrams.entry(bigram.clone()).or_default().add(stats);
+        }
+    }
+
+    fn average_latency(&self) -> Option<f32> {
+        let (total, count) = self.keys.values().fold((0.0, 0), |(total, count), stats| (total + stats.total_latency, count + stats.timed_presses));
+
+        if count == 0 { None } else { Some(total / count as f32) }
+    }
+
+    // 0.0 for a key the player has no trouble with
+    fn weakness(&self, stats: Option<&KeyStats>, average_latency: Option<f32>) -> f32 {
+        let stats = match stats {
+            Some(stats) if stats.presses >= TypingStats::MIN_PRESSES => stats,
+            _ => return 0.0
+        };
+
+        let slowness = match (stats.mean_latency(), average_latency) {
+            (Some(latency), Some(average)) if average > 0.0 => (latency / average - 1.0).max(0.0),
+            _ => 0.0
+        };
+
+        stats.error_rate() + slowness * TypingStats::SLOWNESS_WEIGHT
+    }
+
+    pub fn key_weakness(&self, letter: char) -> f32 {
+        self.weakness(self.keys.get(&letter.to_ascii_lowercase()), self.average_latency())
+    }
+
+    // the weakness of every letter and every pair of letters of the word
+    pub fn word_weakness(&self, word: &str) -> f32 {
+        let average_latency = self.average_latency();
+        let letters = word.to_lowercase().chars().collect::<Vec<char>>();
+
+        let keys = letters.iter().map(|letter| self.weakness(self.keys.get(letter), average_latency)).sum::<f32>();
+        let bigrams = letters.windows(2).map(|pair| {
+            let bigram = pair.iter().collect::<String>();
+            self.weakness(self.bigrams.get(&bigram), average_latency)
+        }).sum::<f32>();
+
+        keys + bigrams
+    }
+
+    // `key<TAB>letter<TAB>presses<TAB>errors<TAB>latency<TAB>timed presses`, the same for `bigram`
+    pub fn serialize(&self) -> String {
+        let line = |kind: &str, name: &str, stats: &KeyStats| {
+            format!("{}\t{}\t{}\t{}\t{}\t{}\n", kind, name, stats.presses, stats.errors, stats.total_latency, stats.timed_presses)
+        };
+
+        self.keys.iter().map(|(key, stats)| line("key", &key.to_string(), stats)).
+            chain(self.bigrams.iter().map(|(bigram, stats)| line("bigram", bigram, stats))).
+            collect()
+    }
+
+    // broken lines are skipped
+    pub fn parse(text: &str) -> Self {
+        let mut stats = TypingStats::default();
+
+        for line in text.lines() {
+            let fields = line.split('\t').collect::<Vec<&str>>();
+
+            if fields.len() != 6 {
+                continue;
+            }
+
+            let key_stats = match (fields[2].parse(), fields[3].parse(), fields[4].parse(), fields[5].parse()) {
+                (Ok(presses), Ok(errors), Ok(total_latency), Ok(timed_presses)) => KeyStats { presses, errors, total_latency, timed_presses },
+                _ => continue
+            };
+
+            let mut name = fields[1].chars();
+
+            match (fields[0], name.next(), name.next()) {
+                ("key", Some(key), None) => { stats.keys.insert(key, key_stats); },
+                ("bigram", Some(_), Some(_)) => { stats.bigrams.insert(fields[1].to_string(), key_stats); },
+                _ => ()
+            }
+        }
+
+        stats
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Improvement {
+    pub key: char,
+    pub error_rate_before: f32,
+    pub error_rate_after: f32,
+    pub latency_before: Option<f32>,
+    pub latency_after: Option<f32>
+}
+
+impl Improvement {
+    pub fn describe(&self) -> String {
+        let mut description = format!("{}: {:.0}% -> {:.0}% errors", self.key, self.error_rate_before * 100.0, self.error_rate_after * 100.0);
+
+        if let (Some(before), Some(after)) = (self.latency_before, self.latency_after) {
+            description.push_str(&format!(", {:.0} -> {:.0} ms", before * 1000.0, after * 1000.0));
+        }
+
+        description
+    }
+}
+
+// keys the player typed better in the session than in the games before it, most improved first
+pub fn improvements(history: &TypingStats, session: &TypingStats) -> Vec<Improvement> {
+    let mut improvements = session.keys.iter().
+        filter_map(|(key, after)| {
+            let before = history.keys.get(key)?;
+
+            if before.presses < TypingStats::MIN_PRESSES || after.presses < TypingStats::MIN_PRESSES {
+                return None;
+            }
+
+            let fewer_errors = after.error_rate() < before.error_rate();
+            let faster = match (before.mean_latency(), after.mean_latency()) {
+                (Some(before), Some(after)) => after < before,
+                _ => false
+            };
+
+            if !(fewer_errors || (faster && after.error_rate() <= before.error_rate())) {
+                return None;
+            }
+
+            Some(Improvement {
+                key: *key,
+                error_rate_before: before.error_rate(),
+                error_rate_after: after.error_rate(),
+                latency_before: before.mean_latency(),
+                latency_after: after.mean_latency()
+            })
+        }).
+        collect::<Vec<Improvement>>();
+
+    let gain = |improvement: &Improvement| {
+        let latency_gain = match (improvement.latency_before, improvement.latency_after) {
+            (Some(before), Some(after)) if before > 0.0 => (before - after) / before,
+            _ => 0.0
+        };
+
+        improvement.error_rate_before - improvement.error_rate_after + latency_gain
+    };
+
+    improvements.sort_by(|first, second| gain(second).total_cmp(&gain(first)));
+    improvements
+}
+
+// dictionary words, picked more often when they have the player's weak keys
+#[derive(Debug)]
+pub struct AdaptiveSource {
+    words: Vec<String>,
+    weights: Option<WeightedIndex<f32>>,
+    rng: StdRng
+}
+
+impl AdaptiveSource {
+    // how many times more likely a word gets for every point of weakness
+    pub const WEAKNESS_WEIGHT: f32 = 4.0;
+
+    pub fn new(words: Vec<String>, stats: &TypingStats, seed: Option<u64>) -> Self {
+        let weights = words.iter().map(|word| 1.0 + stats.word_weakness(word) * AdaptiveSource::WEAKNESS_WEIGHT);
+
+        AdaptiveSource {
+            weights: WeightedIndex::new(weights).ok(),
+            words,
+            rng: word_sources::seeded_rng(seed)
+        }
+    }
+}
+
+impl WordSource for AdaptiveSource {
+    fn name(&self) -> &str {
+        "Adaptive"
+    }
+
+    fn next_word(&mut self) -> Option<String> {
+        let index = self.rng.sample(self.weights.as_ref()?);
+        self.words.get(index).cloned()
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use crate::word_sources::{ DictionarySource, WordSource };
+
+// a dictionary line with two columns, `prompt<TAB>answer`, shows the prompt and expects the answer
+pub const COLUMN_SEPARATOR: char = '\t';
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pair {
+    pub prompt: String,
+    pub answer: String
+}
+
+// the prompt, if the line has one, and the word to type
+pub fn split_line(line: &str) -> (Option<&str>, &str) {
+    match line.split_once(COLUMN_SEPARATOR) {
+        Some((prompt, answer)) => (Some(prompt.trim()), answer.trim()),
+        None => (None, line.trim())
+    }
+}
+
+// the words to type, one column dictionaries give them as they are
+pub fn answers(lines: &[String]) -> Vec<String> {
+    lines.iter().map(|line| split_line(line).1.to_string()).filter(|answer| !answer.is_empty()).collect()
+}
+
+// lines with a single column or an empty column aren't pairs
+pub fn pairs(lines: &[String]) -> Vec<Pair> {
+    lines.iter().
+        filter_map(|line| match split_line(line) {
+            (Some(prompt), answer) if !prompt.is_empty() && !answer.is_empty() => Some(Pair { prompt: prompt.to_string(), answer: answer.to_string() }),
+            _ => None
+        }).
+        collect()
+}
+
+// answers picked like dictionary words, shown with their prompts
+#[derive(Debug)]
+pub struct VocabularySource {
+    // an answer with several prompts keeps the first one
+    prompts: HashMap<String, String>,
+    answers: DictionarySource
+}
+
+impl VocabularySource {
+    pub fn new(pairs: Vec<Pair>, seed: Option<u64>) -> Self {
+        let mut prompts = HashMap::new();
+
+        for pair in pairs {
+            prompts.entry(pair.answer).or_insert(pair.prompt);
+        }
+
+        // sorted, so the same seed gives the same words
+        let mut answers = prompts.keys().cloned().collect::<Vec<String>>();
+        answers.sort();
+
+        VocabularySource {
+            answers: DictionarySource::new(answers, seed),
+            prompts
+        }
+    }
+}
+
+impl WordSource for VocabularySource {
+    fn name(&self) -> &str {
+        "Vocabulary"
+    }
+
+    fn next_word(&mut self) -> Option<String> {
+        self.answers.next_word()
+    }
+
+    fn set_difficulty(&mut self, level: f32) {
+        self.answers.set_difficulty(level);
+    }
+
+    fn prompt(&self, answer: &str) -> Option<String> {
+        self.prompts.get(answer).cloned()
+    }
+}
@@ -0,0 +1,127 @@
+use rand::Rng;
+use rand::rngs::StdRng;
+
+use std::collections::BTreeMap;
+
+use crate::word_sources::{ self, WordSource };
+
+fn first_letter(word: &str) -> Option<char> {
+    word.chars().next().map(|letter| letter.to_ascii_lowercase())
+}
+
+fn last_letter(word: &str) -> Option<char> {
+    word.chars().last().map(|letter| letter.to_ascii_lowercase())
+}
+
+// dictionary words grouped by their first letter, ignoring case
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FirstLetterIndex {
+    by_letter: BTreeMap<char, Vec<String>>
+}
+
+impl FirstLetterIndex {
+    pub fn new(words: &[String]) -> Self {
+        let mut by_letter: BTreeMap<char, Vec<String>> = BTreeMap::new();
+
+        for word in words {
+            if let Some(letter) = first_letter(word) {
+                by_letter.entry(letter).or_default().push(word.clone());
+            }
+        }
+
+        FirstLetterIndex { by_letter }
+    }
+
+    pub fn starting_with(&self, letter: char) -> &[String] {
+        self.by_letter.get(&letter.to_ascii_lowercase()).map(|words| words.as_slice()).unwrap_or(&[])
+    }
+}
+
+// typed words which each start with the last letter of the one before
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Chain {
+    length: u32,
+    next_letter: Option<char>
+}
+
+impl Chain {
+    pub const STEP: f32 = 0.1;
+    pub const MAX_MULTIPLIER: f32 = 3.0;
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    // the letter the next word of the chain starts with, `None` before the first word
+    pub fn next_letter(&self) -> Option<char> {
+        self.next_letter
+    }
+
+    pub fn continues(&self, word: &str) -> bool {
+        match self.next_letter {
+            Some(letter) => first_letter(word) == Some(letter),
+            None => true
+        }
+    }
+
+    // a word out of order breaks the chain and starts a new one, returns whether the chain went on
+    pub fn typed(&mut self, word: &str) -> bool {
+        let continued = self.continues(word);
+
+        self.length = if continued { self.length + 1 } else { 1 };
+        self.next_letter = last_letter(word);
+
+        continued
+    }
+
+    pub fn multiplier(&self) -> f32 {
+        (1.0 + self.length.saturating_sub(1) as f32 * Chain::STEP).min(Chain::MAX_MULTIPLIER)
+    }
+}
+
+// words which continue the chain of the typed words, any word when no word does
+#[derive(Debug)]
+pub struct ChainSource {
+    words: Vec<String>,
+    index: FirstLetterIndex,
+    chain: Chain,
+    rng: StdRng
+}
+
+impl ChainSource {
+    pub fn new(words: Vec<String>, seed: Option<u64>) -> Self {
+        ChainSource {
+            index: FirstLetterIndex::new(&words),
+            words,
+            chain: Chain::default(),
+            rng: word_sources::seeded_rng(seed)
+        }
+    }
+}
+
+impl WordSource for ChainSource {
+    fn name(&self) -> &str {
+        "Word chain"
+    }
+
+    fn next_word(&mut self) -> Option<String> {
+        let unlocked = self.chain.next_letter().map(|letter| self.index.starting_with(letter)).unwrap_or(&[]);
+        let words = if unlocked.is_empty() { &self.words } else { unlocked };
+
+        if words.is_empty() {
+            return None;
+        }
+
+        Some(words[self.rng.gen_range(0 .. words.len())].clone())
+    }
+
+    fn word_result(&mut self, word: &str, typed: bool) {
+        if typed {
+            self.chain.typed(word);
+        }
+    }
+
+    fn chain(&self) -> Option<Chain> {
+        Some(self.chain)
+    }
+}
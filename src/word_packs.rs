@@ -0,0 +1,113 @@
+// a word pack is a directory under `/packs` with a manifest and one or more word files
+pub const PACKS_DIR: &str = "/packs";
+pub const MANIFEST_FILE: &str = "manifest.txt";
+// the pack of the scores saved before there were packs
+pub const DEFAULT_PACK: &str = "english";
+// the packs the game comes with, the daily challenge only picks from these so user packs don't change it
+pub const SHIPPED_PACKS: [&str; 3] = ["english", "programming", "spanish"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Manifest {
+    pub name: String,
+    pub language: String,
+    pub author: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    // paths relative to the pack directory
+    pub word_files: Vec<String>,
+    // optional `word: tag, tag` file for the content filter
+    pub word_tags: Option<String>
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value.split(',').map(|item| item.trim().to_string()).filter(|item| !item.is_empty()).collect()
+}
+
+impl Manifest {
+    // `key: value` lines, lists are separated with commas and lines starting with `#` are skipped
+    pub fn parse(text: &str) -> Result<Manifest, String> {
+        let mut manifest = Manifest {
+            name: String::new(),
+            language: String::new(),
+            author: String::new(),
+            description: String::new(),
+            tags: Vec::new(),
+            word_files: vec![String::from("words.dict")],
+            word_tags: None
+        };
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line.split_once(':').ok_or(format!("line {}: expected 'key: value'", number + 1))?;
+            let value = value.trim();
+
+            match key.trim() {
+                "name" => manifest.name = value.to_string(),
+                "language" => manifest.language = value.to_string(),
+                "author" => manifest.author = value.to_string(),
+                "description" => manifest.description = value.to_string(),
+                "tags" => manifest.tags = split_list(value),
+                "words" => manifest.word_files = split_list(value),
+                "word_tags" => manifest.word_tags = Some(value.to_string()),
+                // unknown keys are left for newer versions of the game
+                _ => ()
+            }
+        }
+
+        if manifest.name.is_empty() {
+            return Err(String::from("the manifest has no name"));
+        }
+
+        if manifest.word_files.is_empty() {
+            return Err(String::from("the manifest has no word files"));
+        }
+
+        Ok(manifest)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordPack {
+    // the directory name, which is what gets saved with scores and settings
+    pub id: String,
+    pub manifest: Manifest
+}
+
+impl WordPack {
+    pub fn manifest_path(id: &str) -> String {
+        format!("{}/{}/{}", PACKS_DIR, id, MANIFEST_FILE)
+    }
+
+    pub fn word_paths(&self) -> Vec<String> {
+        self.manifest.word_files.iter().map(|file| format!("{}/{}/{}", PACKS_DIR, self.id, file)).collect()
+    }
+
+    pub fn word_tags_path(&self) -> Option<String> {
+        self.manifest.word_tags.as_ref().map(|file| format!("{}/{}/{}", PACKS_DIR, self.id, file))
+    }
+
+    // a few lines for the pack selector
+    pub fn summary(&self) -> String {
+        let manifest = &self.manifest;
+        let mut summary = format!("{} ({})", manifest.name, manifest.language);
+
+        if !manifest.author.is_empty() {
+            summary.push_str(&format!(" by {}", manifest.author));
+        }
+
+        if !manifest.description.is_empty() {
+            summary.push_str(&format!("\n{}", manifest.description));
+        }
+
+        if !manifest.tags.is_empty() {
+            summary.push_str(&format!("\nTags: {}", manifest.tags.join(", ")));
+        }
+
+        summary
+    }
+}
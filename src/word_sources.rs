@@ -0,0 +1,254 @@
+use rand::{ Rng, SeedableRng };
+use rand::rngs::StdRng;
+
+use std::collections::{ HashMap, HashSet };
+use std::fmt::Debug;
+
+use crate::difficulty;
+use crate::word_chain::Chain;
+
+// where the spawned words come from
+pub trait WordSource: Debug {
+    fn name(&self) -> &str;
+    // `None` when the source has nothing to give right now
+    fn next_word(&mut self) -> Option<String>;
+    // 0.0 asks for the easiest words, 1.0 for the hardest ones
+    fn set_difficulty(&mut self, _level: f32) {}
+    // called when a spawned word gets typed or escapes
+    fn word_result(&mut self, _word: &str, _typed: bool) {}
+    // the text shown instead of the word, when the word is the answer to it
+    fn prompt(&self, _word: &str) -> Option<String> {
+        None
+    }
+    // the chain of typed words, for the sources which follow one
+    fn chain(&self) -> Option<Chain> {
+        None
+    }
+}
+
+pub(crate) fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy()
+    }
+}
+
+// random words from a word list, close to the requested difficulty
+#[derive(Debug)]
+pub struct DictionarySource {
+    // easiest first
+    words: Vec<String>,
+    level: f32,
+    rng: StdRng
+}
+
+impl DictionarySource {
+    // part of the word list on each side of the requested difficulty which can be picked
+    pub const SELECTION_WINDOW: f32 = 0.2;
+
+    pub fn new(words: Vec<String>, seed: Option<u64>) -> Self {
+        let mut scored = words.into_iter().map(|word| (difficulty::word_difficulty(&word), word)).collect::<Vec<(f32, String)>>();
+        scored.sort_by(|first, second| first.0.total_cmp(&second.0));
+
+        DictionarySource {
+            words: scored.into_iter().map(|(_, word)| word).collect(),
+            level: 0.0,
+            rng: seeded_rng(seed)
+        }
+    }
+
+    // indices of the words which can be picked at the current level
+    pub fn selection_range(&self) -> (usize, usize) {
+        let last = self.words.len().saturating_sub(1) as f32;
+        let center = self.level * last;
+        let half_window = (self.words.len() as f32 * DictionarySource::SELECTION_WINDOW).max(1.0);
+
+        ((center - half_window).max(0.0).round() as usize, (center + half_window).min(last).round() as usize)
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+}
+
+impl WordSource for DictionarySource {
+    fn name(&self) -> &str {
+        "Dictionary"
+    }
+
+    fn next_word(&mut self) -> Option<String> {
+        if self.words.is_empty() {
+            return None;
+        }
+
+        let (first, last) = self.selection_range();
+        self.words.get(self.rng.gen_range(first ..= last)).cloned()
+    }
+
+    fn set_difficulty(&mut self, level: f32) {
+        self.level = level.clamp(0.0, 1.0);
+    }
+}
+
+// the words of a text in their order, starting over at the end
+#[derive(Debug)]
+pub struct PassageSource {
+    words: Vec<String>,
+    next: usize
+}
+
+impl PassageSource {
+    // only letters and dashes can be typed, everything else is dropped
+    pub fn new(text: &str) -> Self {
+        let words = text.split_whitespace().
+            map(|word| word.chars().filter(|letter| letter.is_ascii_alphabetic() || *letter == '-').collect::<String>()).
+            map(|word| word.trim_matches('-').to_string()).
+            filter(|word| !word.is_empty()).
+            collect();
+
+        PassageSource {
+            words,
+            next: 0
+        }
+    }
+}
+
+impl WordSource for PassageSource {
+    fn name(&self) -> &str {
+        "Passage"
+    }
+
+    fn next_word(&mut self) -> Option<String> {
+        let word = self.words.get(self.next).cloned();
+        self.next = if self.words.is_empty() { 0 } else { (self.next + 1) % self.words.len() };
+
+        word
+    }
+}
+
+// pronounceable words which follow the letter patterns of a word list, but aren't in it
+#[derive(Debug)]
+pub struct MarkovGenerator {
+    // letters looked back at when picking the next one
+    order: usize,
+    min_length: usize,
+    max_length: usize,
+    // the last `order` letters of a word and how often each letter followed them
+    transitions: HashMap<String, Vec<(char, u32)>>,
+    known_words: HashSet<String>,
+    rng: StdRng
+}
+
+impl MarkovGenerator {
+    pub const DEFAULT_ORDER: usize = 3;
+    pub const DEFAULT_MIN_LENGTH: usize = 4;
+    pub const DEFAULT_MAX_LENGTH: usize = 9;
+    pub const MAX_ATTEMPTS: usize = 50;
+
+    // marks the start and the end of a word in the transitions
+    const START: char = '^';
+    const END: char = '$';
+
+    pub fn new(words: &[String], order: usize, min_length: usize, max_length: usize, seed: Option<u64>) -> Self {
+        let order = order.max(1);
+        let mut counts: HashMap<String, HashMap<char, u32>> = HashMap::new();
+        let mut known_words = HashSet::new();
+
+        for word in words {
+            let word = word.trim().to_lowercase();
+
+            if word.is_empty() {
+                continue;
+            }
+
+            let padded = std::iter::repeat_n(MarkovGenerator::START, order).
+                chain(word.chars()).
+                chain(std::iter::once(MarkovGenerator::END)).
+                collect::<Vec<char>>();
+
+            for window in padded.windows(order + 1) {
+                let context = window[.. order].iter().collect::<String>();
+                *counts.entry(context).or_default().entry(window[order]).or_default() += 1;
+            }
+
+            known_words.insert(word);
+        }
+
+        // sorted, so the same seed always gives the same words
+        let transitions = counts.into_iter().map(|(context, followers)| {
+            let mut followers = followers.into_iter().collect::<Vec<(char, u32)>>();
+            followers.sort_unstable();
+            (context, followers)
+        }).collect();
+
+        MarkovGenerator {
+            order,
+            min_length,
+            max_length: max_length.max(min_length),
+            transitions,
+            known_words,
+            rng: seeded_rng(seed)
+        }
+    }
+
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    pub fn is_known(&self, word: &str) -> bool {
+        self.known_words.contains(word)
+    }
+
+    fn next_letter(&mut self, context: &str) -> Option<char> {
+        let followers = self.transitions.get(context)?;
+        let total = followers.iter().map(|(_, count)| count).sum::<u32>();
+        let mut roll = self.rng.gen_range(0 .. total);
+
+        for &(letter, count) in followers {
+            if roll < count {
+                return Some(letter);
+            }
+
+            roll -= count;
+        }
+
+        None
+    }
+
+    // a single try, which may end up too short, too long or as a real word
+    fn attempt(&mut self) -> Option<String> {
+        let mut letters = vec![MarkovGenerator::START; self.order];
+
+        loop {
+            let context = letters[letters.len() - self.order ..].iter().collect::<String>();
+
+            match self.next_letter(&context)? {
+                MarkovGenerator::END => break,
+                letter => letters.push(letter)
+            }
+
+            if letters.len() - self.order > self.max_length {
+                return None;
+            }
+        }
+
+        let word = letters[self.order ..].iter().collect::<String>();
+        let length = word.chars().count();
+
+        if length < self.min_length || self.is_known(&word) {
+            return None;
+        }
+
+        Some(word)
+    }
+}
+
+impl WordSource for MarkovGenerator {
+    fn name(&self) -> &str {
+        "Generated"
+    }
+
+    fn next_word(&mut self) -> Option<String> {
+        (0 .. MarkovGenerator::MAX_ATTEMPTS).find_map(|_| self.attempt())
+    }
+}
@@ -0,0 +1,32 @@
+use ggez::mint::Point2;
+use ggez::{ Context, GameResult };
+use ggez::graphics::Color;
+
+use type_racer::assets::Sprite;
+use type_racer::entities::Word;
+
+#[derive(Debug)]
+pub struct MockSprite {
+    pub width: f32,
+    pub height: f32
+}
+
+impl Sprite for MockSprite {
+    fn draw(&mut self, _top_left: Point2<f32>, _color: Color, _ctx: &mut Context) -> GameResult<()> {
+        Ok(())
+    }
+
+    fn width(&self, _ctx: &mut Context) -> f32 {
+        self.width
+    }
+
+    fn height(&self, _ctx: &mut Context) -> f32 {
+        self.height
+    }
+}
+
+#[allow(dead_code)]
+pub fn mock_word(label: &str, x: f32, speed: f32) -> Word {
+    let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0});
+    Word::new(label, Point2 { x, y: 0.0 }, speed, mock_sprite, false).unwrap()
+}
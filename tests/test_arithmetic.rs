@@ -0,0 +1,92 @@
+use quickcheck::quickcheck;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use type_racer::arithmetic::{ self, ArithmeticSource, Operation, TIERS };
+use type_racer::word_sources::WordSource;
+use type_racer::modes::GameMode;
+
+fn solve(prompt: &str) -> u32 {
+    let parts = prompt.split(' ').collect::<Vec<&str>>();
+
+    if parts.len() == 1 {
+        return parts[0].parse().unwrap();
+    }
+
+    let (first, second) = (parts[0].parse::<u32>().unwrap(), parts[2].parse::<u32>().unwrap());
+    match parts[1].chars().next().unwrap() {
+        '+' => first + second,
+        '-' => first - second,
+        '×' => first * second,
+        '÷' => first / second,
+        symbol => panic!("unknown operation {}", symbol)
+    }
+}
+
+#[test]
+fn first_tier_is_plain_numbers() {
+    let mut rng = StdRng::seed_from_u64(1);
+
+    for _ in 0 .. 50 {
+        let problem = arithmetic::generate(&TIERS[0], &mut rng);
+        assert_eq!(problem.prompt, problem.answer);
+        assert!(problem.answer.parse::<u32>().unwrap() <= TIERS[0].max_operand);
+    }
+}
+
+#[test]
+fn tiers_follow_the_difficulty_level() {
+    let mut source = ArithmeticSource::new(Some(2));
+    assert_eq!(source.tier(), 0);
+
+    source.set_difficulty(1.0);
+    assert_eq!(source.tier(), TIERS.len() - 1);
+
+    source.set_difficulty(-3.0);
+    assert_eq!(source.tier(), 0);
+    assert!(TIERS.last().unwrap().operations.contains(&Operation::Divide));
+}
+
+#[test]
+fn source_gives_the_prompt_of_its_last_answer() {
+    let mut source = ArithmeticSource::new(Some(3));
+    source.set_difficulty(1.0);
+
+    let answer = source.next_word().unwrap();
+    let prompt = source.prompt(&answer).unwrap();
+
+    assert_eq!(solve(&prompt).to_string(), answer);
+    assert_eq!(source.prompt("not a number"), None);
+}
+
+#[test]
+fn only_arithmetic_mode_types_digits() {
+    for mode in GameMode::ALL {
+        assert_eq!(mode.types_digits(), mode == GameMode::Arithmetic);
+    }
+}
+
+#[test]
+fn bosses_stay_out_of_modes_without_plain_words() {
+    assert!(GameMode::Classic.spawns_bosses());
+    assert!(GameMode::Daily.spawns_bosses());
+
+    for mode in [GameMode::Vocabulary, GameMode::Arithmetic, GameMode::Memory, GameMode::Campaign] {
+        assert!(!mode.spawns_bosses());
+    }
+}
+
+#[test]
+fn buffs_are_off_where_digits_are_typed() {
+    assert!(!GameMode::Arithmetic.has_power_ups());
+    assert!(GameMode::ALL.iter().filter(|mode| !mode.types_digits()).all(|mode| mode.has_power_ups()));
+}
+
+quickcheck! {
+    fn answers_solve_their_problems(seed: u64, tier: usize) -> bool {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let problem = arithmetic::generate(&TIERS[tier % TIERS.len()], &mut rng);
+
+        problem.answer.chars().all(|digit| digit.is_ascii_digit()) && solve(&problem.prompt).to_string() == problem.answer
+    }
+}
@@ -0,0 +1,102 @@
+use quickcheck::quickcheck;
+
+use type_racer::challenge::{ self, Ruleset };
+use type_racer::cli::{ self, SourceKind };
+use type_racer::modes::GameMode;
+use type_racer::modifiers::ModifierRegistry;
+
+fn names() -> Vec<String> {
+    ModifierRegistry::with_defaults().names()
+}
+
+fn ruleset() -> Ruleset {
+    let names = names();
+
+    Ruleset {
+        seed: 42,
+        mode: GameMode::WordChain,
+        difficulty: 3,
+        source: SourceKind::Generated,
+        order: 2,
+        min_length: 3,
+        max_length: 7,
+        content_filter: true,
+        pack: String::from("programming"),
+        modifiers: vec![names[0].clone(), names[names.len() - 1].clone()]
+    }
+}
+
+#[test]
+fn code_gives_back_its_ruleset() {
+    let code = challenge::encode(&ruleset(), &names());
+
+    assert!(code.chars().all(|letter| letter == '-' || letter.is_ascii_uppercase() || letter.is_ascii_digit()));
+    assert_eq!(challenge::decode(&code, &names()), Ok(ruleset()));
+}
+
+#[test]
+fn dashes_spaces_and_case_are_ignored() {
+    let code = challenge::encode(&ruleset(), &names());
+    let written = format!(" {} ", code.replace('-', " ").to_lowercase());
+
+    assert_eq!(challenge::decode(&written, &names()), Ok(ruleset()));
+}
+
+#[test]
+fn typos_are_refused() {
+    let code = challenge::encode(&ruleset(), &names());
+    let letters = code.chars().collect::<Vec<char>>();
+
+    for index in (0 .. letters.len()).filter(|index| letters[*index] != '-') {
+        let mut typo = letters.clone();
+        typo[index] = if typo[index] == 'A' { 'B' } else { 'A' };
+
+        assert!(challenge::decode(&typo.into_iter().collect::<String>(), &names()).is_err());
+    }
+
+    assert!(challenge::decode("AAAA", &names()).is_err());
+    assert!(challenge::decode("not a code!", &names()).is_err());
+}
+
+#[test]
+fn other_versions_are_refused() {
+    let code = challenge::encode(&ruleset(), &names());
+    // the version is the first byte, the first letter holds its five high bits
+    let mut letters = code.chars().collect::<Vec<char>>();
+    letters[0] = 'B';
+
+    let error = challenge::decode(&letters.into_iter().collect::<String>(), &names()).unwrap_err();
+    assert!(error.contains("checksum") || error.contains("version"));
+}
+
+#[test]
+fn challenge_option_is_parsed() {
+    let code = challenge::encode(&ruleset(), &names());
+    let options = cli::parse(vec![String::from("--challenge"), code]).unwrap();
+
+    assert_eq!(options.challenge, Some(ruleset()));
+    assert_eq!(cli::parse(vec![String::from("--difficulty"), String::from("10")]).unwrap().difficulty, 10);
+    assert!(cli::parse(vec![String::from("--difficulty"), String::from("11")]).is_err());
+    assert!(cli::parse(vec![String::from("--challenge"), String::from("AAAA-AAAA")]).is_err());
+}
+
+quickcheck! {
+    fn any_ruleset_survives_a_round_trip(seed: u64, kinds: (u8, u8, u8), lengths: (u8, u8, u8), content_filter: bool, pack: String, mask: u8) -> bool {
+        let (mode, difficulty, source) = kinds;
+        let names = names();
+        let ruleset = Ruleset {
+            seed,
+            mode: GameMode::ALL[mode as usize % GameMode::ALL.len()],
+            difficulty: difficulty % (challenge::MAX_DIFFICULTY + 1),
+            source: SourceKind::ALL[source as usize % SourceKind::ALL.len()],
+            order: lengths.0,
+            min_length: lengths.1.min(lengths.2),
+            max_length: lengths.1.max(lengths.2),
+            content_filter,
+            pack: pack.chars().take(50).collect(),
+            modifiers: names.iter().enumerate().filter(|(index, _)| mask & (1 << (index % 8)) != 0).map(|(_, name)| name.clone()).collect()
+        };
+
+        challenge::decode(&challenge::encode(&ruleset, &names), &names) == Ok(ruleset)
+    }
+}
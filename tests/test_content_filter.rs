@@ -0,0 +1,55 @@
+use quickcheck::quickcheck;
+
+use type_racer::content_filter::{ ContentFilter, WordTags };
+use type_racer::settings::Settings;
+use type_racer::word_packs::{ Manifest, WordPack };
+
+fn words(words: &[&str]) -> Vec<String> {
+    words.iter().map(|word| word.to_string()).collect()
+}
+
+#[test]
+fn blocked_words_are_removed_and_counted() {
+    let filter = ContentFilter::new("# comment\nHell\n\n  gun  ", &[]);
+    let filtered = filter.apply(words(&["hello", "hell", "Gun", "sun"]), &WordTags::default());
+
+    assert_eq!(filtered.words, words(&["hello", "sun"]));
+    assert_eq!(filtered.removed, 2);
+}
+
+#[test]
+fn words_with_an_excluded_tag_are_removed() {
+    let word_tags = WordTags::parse("beer: alcohol\nkill: violence, Mature\nbroken line");
+    let filter = ContentFilter::new("", &[String::from("mature")]);
+
+    assert_eq!(word_tags.tags("Kill"), ["violence", "mature"]);
+    assert!(word_tags.tags("broken line").is_empty());
+    assert!(filter.allows("beer", &word_tags));
+    assert!(!filter.allows("kill", &word_tags));
+}
+
+#[test]
+fn pack_manifest_points_to_its_tags() {
+    let manifest = Manifest::parse("name: English\nword_tags: tags.txt").unwrap();
+    let pack = WordPack { id: String::from("english"), manifest };
+
+    assert_eq!(pack.word_tags_path(), Some(String::from("/packs/english/tags.txt")));
+}
+
+#[test]
+fn settings_survive_a_round_trip() {
+    let settings = Settings { content_filter: true, excluded_tags: vec![String::from("violence")] };
+
+    assert_eq!(Settings::parse(&settings.serialize()), settings);
+    assert_eq!(Settings::parse("content_filter: maybe"), Settings::default());
+}
+
+quickcheck! {
+    fn filter_keeps_the_rest_in_order(list: Vec<String>, blocked: Vec<String>) -> bool {
+        let filter = ContentFilter::new(&blocked.join("\n"), &[]);
+        let filtered = filter.apply(list.clone(), &WordTags::default());
+        let expected = list.iter().filter(|word| filter.allows(word, &WordTags::default())).cloned().collect::<Vec<String>>();
+
+        filtered.words == expected && filtered.removed + filtered.words.len() == list.len()
+    }
+}
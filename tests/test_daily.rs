@@ -0,0 +1,103 @@
+use quickcheck::quickcheck;
+
+use type_racer::daily::{ self, Date, DailyChallenge, DailyLog, DailyResult };
+use type_racer::modifiers::ModifierRegistry;
+
+fn strings(strings: &[&str]) -> Vec<String> {
+    strings.iter().map(|string| string.to_string()).collect()
+}
+
+fn date(year: i32, month: u32, day: u32) -> Date {
+    Date { year, month, day }
+}
+
+#[test]
+fn dates_match_the_calendar() {
+    assert_eq!(Date::from_days(0), date(1970, 1, 1));
+    assert_eq!(Date::from_days(20_745), date(2026, 10, 19));
+    assert_eq!(date(2026, 10, 19).weekday(), 0);
+    assert_eq!(Date::days_in_month(2024, 2), 29);
+    assert_eq!(Date::days_in_month(2100, 2), 28);
+    assert_eq!(Date::parse("2026-10-19"), Some(date(2026, 10, 19)));
+    assert_eq!(Date::parse("2026-02-30"), None);
+    assert_eq!(date(2026, 1, 5).to_string(), "2026-01-05");
+}
+
+#[test]
+fn month_starts_on_its_weekday() {
+    // October 2026 starts on a Thursday and takes five weeks
+    let weeks = daily::month_weeks(2026, 10);
+
+    assert_eq!(weeks.len(), 5);
+    assert_eq!(weeks[0], [None, None, None, Some(1), Some(2), Some(3), Some(4)]);
+    assert_eq!(weeks[4][5], Some(31));
+}
+
+#[test]
+fn challenge_is_the_same_for_the_same_day() {
+    let packs = strings(&["english", "programming", "spanish"]);
+    let modifiers = strings(&["Fog", "Reversed words", "Scrambled words", "Screen shake", "Fading words"]);
+
+    let today = DailyChallenge::new(date(2026, 10, 19), &packs, &modifiers);
+    assert_eq!(today, DailyChallenge::new(date(2026, 10, 19), &packs, &modifiers));
+    assert_eq!(today.modifiers.len(), DailyChallenge::MODIFIER_COUNT);
+    assert!(today.difficulty <= DailyChallenge::MAX_DIFFICULTY);
+    assert!(packs.contains(&today.pack));
+
+    let seeds = (0 .. 30).map(|day| daily::day_seed(Date::from_days(day))).collect::<std::collections::HashSet<u64>>();
+    assert_eq!(seeds.len(), 30);
+}
+
+#[test]
+fn only_the_first_attempt_of_the_day_is_ranked() {
+    let mut log = DailyLog::default();
+    let today = date(2026, 10, 19);
+
+    assert!(log.record(DailyResult { date: today, profile: String::from("ana"), score: 10.0 }));
+    assert!(!log.record(DailyResult { date: today, profile: String::from("ana"), score: 99.0 }));
+    assert!(log.record(DailyResult { date: today, profile: String::from("bob"), score: 20.0 }));
+    assert!(log.record(DailyResult { date: date(2026, 10, 20), profile: String::from("ana"), score: 5.0 }));
+
+    let names = log.day_results(today).iter().map(|result| result.profile.as_str()).collect::<Vec<&str>>();
+    assert_eq!(names, ["bob", "ana"]);
+    assert_eq!(log.history("ana").get(&today), Some(&10.0));
+    assert_eq!(DailyLog::parse(&log.serialize()), log);
+}
+
+#[test]
+fn ranked_attempt_gets_its_score_at_the_end() {
+    let mut log = DailyLog::default();
+    let today = date(2026, 10, 19);
+
+    // recorded when the run starts, a second start isn't ranked
+    assert!(log.record(DailyResult { date: today, profile: String::from("ana"), score: 0.0 }));
+    assert!(log.has_ranked(today, "ana"));
+    assert!(!log.record(DailyResult { date: today, profile: String::from("ana"), score: 0.0 }));
+
+    log.set_score(today, "ana", 42.0);
+    log.set_score(today, "bob", 99.0);
+    assert_eq!(log.history("ana").get(&today), Some(&42.0));
+    assert!(!log.has_ranked(today, "bob"));
+
+    let options = type_racer::cli::parse(vec![String::from("--profile"), String::from("ana")]).unwrap();
+    assert_eq!(options.profile, Some(String::from("ana")));
+    assert!(type_racer::cli::parse(vec![String::from("--profile"), String::from(" ")]).is_err());
+}
+
+#[test]
+fn kept_modifiers_are_the_named_ones() {
+    let mut registry = ModifierRegistry::with_defaults();
+    registry.keep(&strings(&["Speed-up", "Fog"]));
+
+    let names = registry.modifiers().iter().map(|modifier| modifier.name()).collect::<Vec<&str>>();
+    assert_eq!(names, ["Speed-up", "Fog"]);
+}
+
+quickcheck! {
+    fn days_survive_a_round_trip(days: i32) -> bool {
+        let date = Date::from_days(days as i64);
+
+        // years before 0 aren't written as `YYYY`
+        date.days() == days as i64 && (date.year < 0 || Date::parse(&date.to_string()) == Some(date))
+    }
+}
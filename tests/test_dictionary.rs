@@ -0,0 +1,58 @@
+use quickcheck::quickcheck;
+
+use type_racer::dictionary::*;
+use type_racer::cli::{ self, Command };
+
+const MESSY: &str = "apple\r\nbanana\n\ncafé\napple\n  cherry  \nice-cream\n";
+
+#[test]
+fn problems_are_reported_with_their_lines() {
+    let report = validate(MESSY);
+
+    assert_eq!(report.lines, 7);
+    assert_eq!(report.words, 4);
+    assert_eq!(report.crlf_lines, 1);
+    assert_eq!(report.blank_lines, vec![3]);
+    assert_eq!(report.duplicates, vec![Duplicate { word: String::from("apple"), lines: vec![1, 5] }]);
+    assert_eq!(report.untypable, vec![Untypable { line: 4, word: String::from("café"), letters: vec!['é'] }]);
+    assert!(!report.is_clean());
+}
+
+#[test]
+fn length_distribution_counts_distinct_words() {
+    let report = validate("cat\ndog\ncat\nhorse");
+
+    assert_eq!(report.length_distribution.get(&3), Some(&2));
+    assert_eq!(report.length_distribution.get(&5), Some(&1));
+    assert!(report.to_string().contains("length distribution"));
+}
+
+#[test]
+fn normalized_dictionary_is_clean() {
+    let normalized = normalize(MESSY);
+
+    assert_eq!(normalized, "apple\nbanana\ncherry\nice-cream\n");
+    assert!(validate(&normalized).is_clean());
+    assert_eq!(validate("").lines, 0);
+}
+
+#[test]
+fn validate_subcommand_is_parsed() {
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>();
+
+    assert_eq!(cli::parse_command(args(&["validate", "words.dict"])),
+               Ok(Command::Validate { path: String::from("words.dict"), output: None }));
+    assert_eq!(cli::parse_command(args(&["validate", "words.dict", "--write", "clean.dict"])),
+               Ok(Command::Validate { path: String::from("words.dict"), output: Some(String::from("clean.dict")) }));
+    assert!(cli::parse_command(args(&["validate"])).is_err());
+    assert!(cli::parse_command(args(&["validate", "words.dict", "--write"])).is_err());
+    assert!(matches!(cli::parse_command(args(&["--seed", "1"])), Ok(Command::Play(_))));
+}
+
+quickcheck! {
+    fn normalizing_twice_changes_nothing(text: String) -> bool {
+        let normalized = normalize(&text);
+
+        normalize(&normalized) == normalized && validate(&normalized).is_clean()
+    }
+}
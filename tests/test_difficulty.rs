@@ -0,0 +1,39 @@
+use quickcheck::quickcheck;
+
+use type_racer::difficulty::*;
+
+#[test]
+fn same_hand_pairs_are_counted() {
+    // every letter of "were" is on the left hand, "hop" is all right hand
+    assert_eq!(same_hand_pairs("were"), 3);
+    assert_eq!(same_hand_pairs("hop"), 2);
+    // alternating hands
+    assert_eq!(same_hand_pairs("tidy"), 0);
+}
+
+#[test]
+fn same_finger_bigrams_are_awkward() {
+    assert_eq!(awkward_bigrams("ced"), 2);
+    assert_eq!(awkward_bigrams("Bread"), 1);
+    // double letters are typed with a single key
+    assert_eq!(awkward_bigrams("moon"), 0);
+}
+
+#[test]
+fn awkward_words_are_harder_than_plain_ones() {
+    assert_eq!(word_difficulty(""), 0.0);
+    assert!(word_difficulty("jazz") > word_difficulty("moon"));
+    assert!(word_difficulty("decrypt") > word_difficulty("kitchen"));
+}
+
+quickcheck! {
+    fn difficulty_is_at_least_the_length(label: String) -> bool {
+        word_difficulty(&label) >= label.chars().count() as f32
+    }
+
+    fn longer_word_is_never_easier(label: String, letter: char) -> bool {
+        let longer = format!("{}{}", label, letter);
+
+        word_difficulty(&longer) > word_difficulty(&label)
+    }
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use ggez::graphics::Rect;
+use ggez::mint::Vector2;
+use quickcheck::quickcheck;
+
+use type_racer::duplicates::DuplicatePolicy;
+use type_racer::entities::Word;
+use type_racer::movement::Linear;
+
+use common::mock_word;
+
+const FIELD: Rect = Rect { x: 0.0, y: 0.0, w: 1000.0, h: 1000.0 };
+
+// same size as the mock sprite
+fn rects(words: &[Word]) -> Vec<Rect> {
+    words.iter().map(|word| Rect::new(word.pos.x, word.pos.y, 100.0, 100.0)).collect()
+}
+
+fn targets(policy: DuplicatePolicy, words: &[Word], input: &str) -> Vec<usize> {
+    policy.targets(words, &rects(words), input, FIELD)
+}
+
+#[test]
+fn nearest_to_edge_hits_the_word_closest_to_escaping() {
+    let words = vec![mock_word("cat", 100.0, 100.0), mock_word("cat", 500.0, 100.0), mock_word("dog", 800.0, 100.0)];
+
+    assert_eq!(targets(DuplicatePolicy::NearestToEdge, &words, "cat"), vec![1]);
+}
+
+#[test]
+fn nearest_to_edge_compares_words_moving_in_different_directions() {
+    let mut left_word = mock_word("cat", 200.0, 100.0);
+    left_word.set_movement(Box::new(Linear { velocity: Vector2 { x: -100.0, y: 0.0 } }));
+    let words = vec![mock_word("cat", 600.0, 100.0), left_word];
+
+    // 400 pixels to the right edge against 300 pixels to the left edge
+    assert_eq!(targets(DuplicatePolicy::NearestToEdge, &words, "cat"), vec![1]);
+}
+
+#[test]
+fn all_duplicates_die_together() {
+    let words = vec![mock_word("cat", 100.0, 100.0), mock_word("dog", 300.0, 100.0), mock_word("cat", 500.0, 100.0)];
+
+    assert_eq!(targets(DuplicatePolicy::AllDie, &words, "cat"), vec![0, 2]);
+}
+
+#[test]
+fn no_duplicates_blocks_spawning_a_word_on_the_screen() {
+    let words = vec![mock_word("cat", 100.0, 100.0)];
+
+    assert!(!DuplicatePolicy::NoDuplicates.allows_spawn(&words, "cat"));
+    assert!(DuplicatePolicy::NoDuplicates.allows_spawn(&words, "dog"));
+    assert!(DuplicatePolicy::NearestToEdge.allows_spawn(&words, "cat"));
+    assert!(DuplicatePolicy::AllDie.allows_spawn(&words, "cat"));
+}
+
+#[test]
+fn no_duplicates_still_hits_a_single_word() {
+    let words = vec![mock_word("cat", 100.0, 100.0), mock_word("cat", 500.0, 100.0)];
+
+    assert_eq!(targets(DuplicatePolicy::NoDuplicates, &words, "cat"), vec![1]);
+}
+
+#[test]
+fn typed_words_are_never_hit_again() {
+    let mut words = vec![mock_word("cat", 100.0, 100.0), mock_word("cat", 500.0, 100.0)];
+    words[1].is_typed = true;
+
+    for policy in DuplicatePolicy::ALL {
+        assert_eq!(targets(policy, &words, "cat"), vec![0]);
+    }
+
+    assert!(DuplicatePolicy::NoDuplicates.allows_spawn(&words[1 ..], "cat"));
+}
+
+#[test]
+fn typo_counts_only_for_the_word_being_typed() {
+    let words = vec![mock_word("cat", 100.0, 100.0), mock_word("car", 500.0, 100.0), mock_word("dog", 800.0, 100.0)];
+
+    assert_eq!(DuplicatePolicy::typed_word(&words, &rects(&words), "ca", FIELD), Some(1));
+    assert_eq!(DuplicatePolicy::typed_word(&words, &rects(&words), "do", FIELD), Some(2));
+    assert_eq!(DuplicatePolicy::typed_word(&words, &rects(&words), "x", FIELD), None);
+}
+
+#[test]
+fn policies_cycle_through_every_option() {
+    let mut policy = DuplicatePolicy::default();
+
+    for _ in 0 .. DuplicatePolicy::ALL.len() {
+        policy = policy.next();
+    }
+
+    assert_eq!(policy, DuplicatePolicy::default());
+}
+
+quickcheck! {
+    fn only_matching_words_are_hit(positions: Vec<u16>, input: bool) -> bool {
+        let words = positions.iter().
+            enumerate().
+            map(|(index, &x)| mock_word(if index % 2 == 0 { "cat" } else { "dog" }, (x % 1000) as f32, 100.0)).
+            collect::<Vec<Word>>();
+        let input = if input { "cat" } else { "ca" };
+        let matching = words.iter().filter(|word| word.label() == input).count();
+
+        let nearest = targets(DuplicatePolicy::NearestToEdge, &words, input);
+        let all = targets(DuplicatePolicy::AllDie, &words, input);
+
+        nearest.len() == matching.min(1) &&
+        all.len() == matching &&
+        all.iter().all(|&index| words[index].label() == input)
+    }
+}
@@ -1,37 +1,23 @@
-use ggez::mint::Point2;
-use ggez::{ Context, GameResult };
-use ggez::graphics::Color;
+mod common;
+
+use ggez::graphics::Rect;
+use ggez::mint::{ Point2, Vector2 };
 use quickcheck::quickcheck;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 
 use type_racer::entities::*;
-use type_racer::assets::Sprite;
-
-#[derive(Debug)]
-struct MockSprite {
-    width: f32,
-    height: f32
-}
-
-impl Sprite for MockSprite {
-    fn draw(&mut self, _top_left: Point2<f32>, _color: Color, _ctx: &mut Context) -> GameResult<()> {
-        Ok(())
-    }
-
-    fn width(&self, _ctx: &mut Context) -> f32 {
-        self.width
-    }
+use type_racer::movement::*;
+use type_racer::difficulty;
 
-    fn height(&self, _ctx: &mut Context) -> f32 {
-        self.height
-    }
-}
+use common::MockSprite;
 
 quickcheck! {
     fn words_move_left(x: f32, y: f32) -> bool {
         let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0});
         let mut word = Word::new("something", Point2 { x, y }, 10.0, mock_sprite, false).unwrap();
 
-        let old_pos = word.pos.clone();
+        let old_pos = word.pos;
         word.update(10.0);
 
         word.pos.x > old_pos.x && word.pos.y == old_pos.y
@@ -45,7 +31,7 @@ quickcheck! {
         };
         let word = Word::new(&label, point, 10.0, mock_sprite, false).unwrap();
 
-        word.label() == &label
+        word.label() == label
     }
 
     fn word_translate(x: f32, y: f32) -> bool {
@@ -84,19 +70,209 @@ quickcheck! {
             x: 0.0,
             y: 0.0
         };
-        let mut word = Word::new(&label, point, speed, mock_sprite, color_changing).unwrap();
+        let word = Word::new(&label, point, speed, mock_sprite, color_changing).unwrap();
         let reward = word.get_reward();
-        let color_multiplayer = {
-            if color_changing {
-                2.0;
-            }
+        let color_multiplayer = if color_changing { 2.0 } else { 1.0 };
+
+        let expected_reward = speed * color_multiplayer * difficulty::word_difficulty(&label) / 100.0;
+
 
-            1.0
+      (reward - expected_reward).abs() < f32::EPSILON
+    }
+
+    fn reversed_word_is_shown_backwards(label: String) -> bool {
+        let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0});
+        let point = Point2 {
+            x: 0.0,
+            y: 0.0
         };
+        let mut word = Word::new(&label, point, 10.0, mock_sprite, false).unwrap();
+        word.is_reversed = true;
+        word.update_shown_label("");
 
-        let expected_reward = speed * color_multiplayer * (label.len() as f32) / 100.0;
+        word.shown_label() == label.chars().rev().collect::<String>() && word.label() == label
+    }
 
+    fn same_seed_scrambles_the_same_way(label: String, seed: u64) -> bool {
+        let scrambled = || {
+            let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0});
+            let mut word = Word::new(&label, Point2 { x: 0.0, y: 0.0 }, 10.0, mock_sprite, false).unwrap();
+            word.scramble(&mut StdRng::seed_from_u64(seed));
+            word.update_shown_label("");
+            word.shown_label().to_string()
+        };
 
-      (reward - expected_reward).abs() < f32::EPSILON
+        scrambled() == scrambled()
+    }
+
+    fn scrambled_word_unscrambles_typed_prefix(label: String, typed: usize) -> bool {
+        let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0});
+        let point = Point2 {
+            x: 0.0,
+            y: 0.0
+        };
+        let mut word = Word::new(&label, point, 10.0, mock_sprite, false).unwrap();
+        word.scramble(&mut rand::thread_rng());
+
+        let typed = typed % (label.chars().count() + 1);
+        let prefix = label.chars().take(typed).collect::<String>();
+        word.update_shown_label(&prefix);
+
+        let mut shown = word.shown_label().chars().collect::<Vec<char>>();
+        let mut expected = label.chars().collect::<Vec<char>>();
+        let starts_with_prefix = word.shown_label().starts_with(&prefix);
+        shown.sort_unstable();
+        expected.sort_unstable();
+
+        starts_with_prefix && shown == expected
     }
-}
\ No newline at end of file
+
+    fn fading_word_disappears(fade_time: f32, seconds: f32) -> bool {
+        let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0});
+        let point = Point2 {
+            x: 0.0,
+            y: 0.0
+        };
+        let mut word = Word::new("fade", point, 10.0, mock_sprite, false).unwrap();
+        let fade_time = fade_time.abs() + 1.0;
+        word.fade_time = Some(fade_time);
+        word.update(seconds.abs());
+
+        let visibility = word.visibility();
+        (0.0 ..= 1.0).contains(&visibility) && (seconds.abs() < fade_time || visibility == 0.0)
+    }
+
+    fn sinusoidal_words_move_right_within_amplitude(y: f32, amplitude: f32, seconds: f32) -> bool {
+        let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0});
+        let mut word = Word::new("something", Point2 { x: 0.0, y }, 10.0, mock_sprite, false).unwrap();
+        let amplitude = amplitude.abs() % 100.0;
+        word.set_movement(Box::new(Sinusoidal { velocity: Vector2 { x: 10.0, y: 0.0 }, amplitude, frequency: 0.5 }));
+
+        word.update(seconds.abs() % 100.0 + 0.1);
+
+        word.pos.x > 0.0 && (word.pos.y - y).abs() <= 2.0 * amplitude + 0.01
+    }
+
+    fn zig_zag_words_move_right_within_amplitude(y: f32, amplitude: f32, seconds: f32) -> bool {
+        let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0});
+        let mut word = Word::new("something", Point2 { x: 0.0, y }, 10.0, mock_sprite, false).unwrap();
+        let amplitude = amplitude.abs() % 100.0;
+        word.set_movement(Box::new(ZigZag { velocity: Vector2 { x: 10.0, y: 0.0 }, amplitude, period: 2.0 }));
+
+        word.update(seconds.abs() % 100.0 + 0.1);
+
+        word.pos.x > 0.0 && (word.pos.y - y).abs() <= 2.0 * amplitude + 0.01
+    }
+
+    fn accelerating_words_speed_up(acceleration: f32) -> bool {
+        let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0});
+        let mut word = Word::new("something", Point2 { x: 0.0, y: 0.0 }, 10.0, mock_sprite, false).unwrap();
+        let acceleration = acceleration.abs() % 10.0 + 0.1;
+        word.set_movement(Box::new(Accelerating { velocity: Vector2 { x: 10.0, y: 0.0 }, acceleration }));
+
+        word.update(1.0);
+        let first_step = word.pos.x;
+        word.update(1.0);
+        let second_step = word.pos.x - first_step;
+
+        first_step > 0.0 && second_step > first_step
+    }
+
+    fn curved_words_bend_to_the_given_side(bend: f32) -> bool {
+        let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0});
+        let mut word = Word::new("something", Point2 { x: 0.0, y: 0.0 }, 10.0, mock_sprite, false).unwrap();
+        let bend = if bend == 0.0 { 1.0 } else { bend % 50.0 };
+        word.set_movement(Box::new(Curved { velocity: Vector2 { x: 10.0, y: 0.0 }, bend }));
+
+        word.update(2.0);
+
+        word.pos.x > 0.0 && word.pos.y.signum() == bend.signum()
+    }
+
+    fn words_from_every_side_escape_the_screen(side: u8, speed: f32) -> bool {
+        let side = [SpawnSide::Left, SpawnSide::Right, SpawnSide::Top][side as usize % 3];
+        let speed = speed.abs() % 300.0 + 100.0;
+        let screen = Rect::new(0.0, 0.0, 1200.0, 1000.0);
+        let start = match side {
+            SpawnSide::Left => Point2 { x: 0.0, y: 500.0 },
+            SpawnSide::Right => Point2 { x: 1100.0, y: 500.0 },
+            SpawnSide::Top => Point2 { x: 600.0, y: 0.0 }
+        };
+
+        let mock_sprite = Box::new(MockSprite { width: 100.0, height: 30.0});
+        let mut word = Word::new("something", start, speed, mock_sprite, false).unwrap();
+        word.set_movement(Box::new(Linear { velocity: side.velocity(speed) }));
+        let rect = |word: &Word| Rect::new(word.pos.x, word.pos.y, 100.0, 30.0);
+
+        let escaped_at_start = word.heading().has_escaped(rect(&word), screen);
+        word.update(20.0);
+
+        !escaped_at_start && word.heading().has_escaped(rect(&word), screen)
+    }
+
+    fn picked_patterns_are_unlocked(difficulty: f32) -> bool {
+        let mut rng = rand::thread_rng();
+        let difficulty = difficulty.abs() % 0.3;
+
+        (0 .. 20).all(|_| pick_pattern(difficulty, &mut rng) == Pattern::Linear &&
+                          pick_side(difficulty, &mut rng) == SpawnSide::Left)
+    }
+
+    fn armored_word_needs_every_hit(hits: u8) -> bool {
+        let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0});
+        let mut word = Word::new("armor", Point2 { x: 0.0, y: 0.0 }, 10.0, mock_sprite, false).unwrap();
+        let hits = (hits % 5) as u32 + 1;
+        word.kind = WordKind::Armored { hits_left: hits };
+
+        let survived = (1 .. hits).all(|_| !word.hit() && !word.is_typed);
+
+        survived && word.hit() && word.is_typed
+    }
+
+    fn splitting_word_halves_make_the_label(label: String) -> bool {
+        let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0});
+        let mut word = Word::new(&label, Point2 { x: 0.0, y: 0.0 }, 10.0, mock_sprite, false).unwrap();
+        word.kind = WordKind::Splitting;
+
+        match word.split_labels() {
+            Some((first, second)) => !first.is_empty() && !second.is_empty() && first + &second == label,
+            None => label.chars().count() < WordKind::MIN_SPLIT_LENGTH
+        }
+    }
+
+    fn escape_cost_depends_on_kind(lives: u32) -> bool {
+        let word_with_kind = |kind: WordKind| {
+            let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0});
+            let mut word = Word::new("kind", Point2 { x: 0.0, y: 0.0 }, 10.0, mock_sprite, false).unwrap();
+            word.kind = kind;
+            word
+        };
+
+        word_with_kind(WordKind::Normal).escape_cost() == 1 &&
+        word_with_kind(WordKind::Golden).escape_cost() == 0 &&
+        word_with_kind(WordKind::Golden).cash_multiplier() == WordKind::GOLDEN_CASH_MULTIPLIER &&
+        word_with_kind(WordKind::Boss { lives }).escape_cost() == lives
+    }
+
+    fn picked_kind_is_never_a_boss(label: String) -> bool {
+        let mut rng = rand::thread_rng();
+
+        (0 .. 20).all(|_| match WordKind::pick(&label, &mut rng) {
+            WordKind::Boss { .. } => false,
+            WordKind::Splitting => label.chars().count() >= WordKind::MIN_SPLIT_LENGTH,
+            _ => true
+        })
+    }
+
+    fn flashing_word_hides_after_its_time(seconds: f32) -> bool {
+        let seconds = seconds.abs() % 10.0;
+        let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0});
+        let mut word = Word::new("flash", Point2 { x: 0.0, y: 0.0 }, 10.0, mock_sprite, false).unwrap();
+        word.visibility_state = Visibility::Flash { seconds };
+
+        let shown = !word.is_hidden() && word.visibility() == 1.0;
+        word.update(seconds + 0.1);
+
+        shown && word.is_hidden() && word.visibility() == 0.0 && word.label() == "flash"
+    }
+}
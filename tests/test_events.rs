@@ -0,0 +1,93 @@
+use quickcheck::quickcheck;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use type_racer::events::{ EventConfig, EventScheduler, GoldRush, WorldEvent };
+use type_racer::game_state::GameState;
+use type_racer::modes::GameMode;
+
+fn state(seed: u64) -> GameState {
+    let mut state = GameState::new();
+    state.rng = StdRng::seed_from_u64(seed);
+    state
+}
+
+fn gold_rush() -> EventScheduler {
+    let mut scheduler = EventScheduler::new();
+    scheduler.register(Box::new(GoldRush { cash_multiplier: 2.0 }));
+    scheduler
+}
+
+#[test]
+fn events_are_announced_before_they_start() {
+    let mut state = state(1);
+    let mut scheduler = gold_rush();
+
+    scheduler.update(EventScheduler::FIRST_EVENT - 1.0, &mut state);
+    assert!(scheduler.warning().is_none());
+
+    scheduler.update(1.0, &mut state);
+    let (event, remaining) = scheduler.warning().unwrap();
+    assert_eq!(event.name(), "Gold Rush");
+    assert_eq!(remaining, EventScheduler::WARNING_TIME);
+    assert_eq!(state.cash_multiplier, 1.0);
+
+    scheduler.update(EventScheduler::WARNING_TIME, &mut state);
+    assert!(scheduler.warning().is_none());
+    assert_eq!(scheduler.running().unwrap().0.name(), "Gold Rush");
+    assert_eq!(state.cash_multiplier, 2.0);
+}
+
+#[test]
+fn events_undo_their_changes_when_they_end() {
+    let mut state = state(2);
+    let mut scheduler = gold_rush();
+    let duration = GoldRush { cash_multiplier: 2.0 }.duration();
+
+    for seconds in [EventScheduler::FIRST_EVENT, EventScheduler::WARNING_TIME, duration] {
+        scheduler.update(seconds, &mut state);
+    }
+
+    assert!(scheduler.running().is_none());
+    assert_eq!(state.cash_multiplier, 1.0);
+
+    // the next one waits for the gap
+    scheduler.update(EventScheduler::MIN_GAP - 1.0, &mut state);
+    assert!(scheduler.warning().is_none());
+}
+
+#[test]
+fn modes_get_their_configured_events() {
+    let config = EventConfig::parse("# comment\nclassic: Rush Hour, Blackout\ncampaign: none\n");
+
+    assert_eq!(EventScheduler::for_mode(GameMode::Classic, &config).names(), vec![String::from("Rush Hour"), String::from("Blackout")]);
+    assert!(EventScheduler::for_mode(GameMode::Campaign, &config).names().is_empty());
+    assert_eq!(EventScheduler::for_mode(GameMode::Daily, &config).names(), EventScheduler::with_defaults().names());
+}
+
+#[test]
+fn modes_without_events_stay_calm() {
+    let mut state = state(3);
+    let mut scheduler = EventScheduler::for_mode(GameMode::Campaign, &EventConfig::parse("campaign: none"));
+
+    for _ in 0 .. 100 {
+        scheduler.update(10.0, &mut state);
+    }
+
+    assert!(scheduler.warning().is_none() && scheduler.running().is_none());
+}
+
+quickcheck! {
+    fn only_a_running_event_changes_the_game(seed: u64, steps: Vec<u8>) -> bool {
+        let mut state = state(seed);
+        let mut scheduler = EventScheduler::with_defaults();
+
+        steps.iter().all(|step| {
+            scheduler.update(*step as f32 / 10.0, &mut state);
+            let calm = state.spawn_rate == 1.0 && state.cash_multiplier == 1.0 && !state.blackout;
+
+            scheduler.running().is_some() || calm
+        })
+    }
+}
@@ -0,0 +1,50 @@
+use ggez::graphics::Rect;
+use quickcheck::quickcheck;
+
+use type_racer::lanes::Lanes;
+
+#[test]
+fn word_inside_a_lane_occupies_only_that_lane() {
+    let lanes = Lanes::new(0.0, 400.0, 40.0);
+    let word = lanes.lane_rect(3, 50.0, 150.0);
+
+    let occupied = lanes.occupied((0.0, 300.0), &[word]);
+
+    assert_eq!(lanes.count(), 10);
+    assert_eq!(occupied.iter().filter(|&&lane| lane).count(), 1);
+    assert!(occupied[3]);
+}
+
+#[test]
+fn words_outside_the_spawn_zone_are_ignored() {
+    let lanes = Lanes::new(0.0, 400.0, 40.0);
+    let word = Rect::new(500.0, 0.0, 100.0, 40.0);
+
+    assert!(lanes.occupied((0.0, 300.0), &[word]).iter().all(|&lane| !lane));
+}
+
+quickcheck! {
+    fn picked_lane_is_free(occupied: Vec<bool>) -> bool {
+        if occupied.is_empty() {
+            return true;
+        }
+
+        let lanes = Lanes::new(0.0, 40.0 * occupied.len() as f32, 40.0);
+        let mut rng = rand::thread_rng();
+
+        match lanes.pick_free(&occupied, &mut rng) {
+            Some(lane) => !occupied[lane],
+            None => occupied.iter().all(|&lane| lane)
+        }
+    }
+
+    fn lanes_fit_in_the_field(top: u16, height: u16, lane_height: u8) -> bool {
+        let top = top as f32;
+        let lane_height = lane_height as f32 + 1.0;
+        let bottom = top + height as f32 + lane_height;
+        let lanes = Lanes::new(top, bottom, lane_height);
+        let last = lanes.count() - 1;
+
+        lanes.lane_top(0) == top && lanes.lane_top(last) + lanes.lane_height() <= bottom
+    }
+}
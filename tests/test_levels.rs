@@ -0,0 +1,150 @@
+use quickcheck::quickcheck;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use std::collections::BTreeMap;
+
+use type_racer::entities::WordKind;
+use type_racer::levels::{ self, Level, LevelRun, CampaignProgress };
+
+const LEVEL: &str = "
+# a comment
+name: Test
+description: Two waves
+pack: english
+stars: 10, 20, 30
+
+wave
+words: one, two
+cadence: 1
+speed: 100
+pause: 5
+
+wave
+count: 3
+kinds: armored, golden
+cadence: 0.5 .. 1.0
+speed: 150 .. 200
+modifiers: Fog, Speed-up
+pause: 0
+";
+
+// the words the run spawns in `seconds`, one update per frame
+fn run_for(run: &mut LevelRun, seconds: f32, rng: &mut StdRng) -> Vec<(f32, Option<String>)> {
+    let frame = 0.1;
+    let mut spawned = Vec::new();
+
+    for step in 0 .. (seconds / frame).round() as usize {
+        run.take_started_wave();
+
+        if let Some(word) = run.update(frame, rng) {
+            spawned.push((step as f32 * frame, word.word));
+        }
+    }
+
+    spawned
+}
+
+#[test]
+fn level_script_is_parsed() {
+    let level = Level::parse("01-test", LEVEL).unwrap();
+
+    assert_eq!(level.id, "01-test");
+    assert_eq!(level.name, "Test");
+    assert_eq!(level.pack, "english");
+    assert_eq!(level.stars, [10.0, 20.0, 30.0]);
+    assert_eq!(level.waves.len(), 2);
+
+    assert_eq!(level.waves[0].count, 2);
+    assert_eq!(level.waves[0].words, vec![String::from("one"), String::from("two")]);
+    assert_eq!(level.waves[0].cadence, (1.0, 1.0));
+    assert_eq!(level.waves[0].pause, 5.0);
+
+    assert_eq!(level.waves[1].count, 3);
+    assert_eq!(level.waves[1].kinds, vec![WordKind::Armored { hits_left: WordKind::ARMOR_HITS }, WordKind::Golden]);
+    assert_eq!(level.waves[1].speed, (150.0, 200.0));
+    assert_eq!(level.waves[1].modifiers, vec![String::from("Fog"), String::from("Speed-up")]);
+}
+
+#[test]
+fn broken_scripts_are_refused() {
+    assert!(Level::parse("a", "name: No waves").is_err());
+    assert!(Level::parse("a", "wave\ncount: 3").is_err());
+    assert!(Level::parse("a", "name: A\nwave\nkinds: boss").is_err());
+    assert!(Level::parse("a", "name: A\nwave\nspeed: 200 .. 100").is_err());
+    assert!(Level::parse("a", "name: A\nwave\npack: english").is_err());
+    assert!(Level::parse("a", "name: A\nstars: 30, 20, 10\nwave").is_err());
+    assert!(Level::parse("a", "name: A\nwave\nmodifiers: Fog, Speedup").unwrap_err().contains("Speedup"));
+
+    let error = Level::parse("a", "name: A\nwave\ncadence: soon").unwrap_err();
+    assert!(error.starts_with("line 3"));
+}
+
+#[test]
+fn waves_spawn_in_order_with_pauses() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut run = LevelRun::new(Level::parse("test", LEVEL).unwrap());
+
+    let spawned = run_for(&mut run, 20.0, &mut rng);
+    let words = spawned.iter().map(|(_, word)| word.clone()).collect::<Vec<Option<String>>>();
+
+    assert_eq!(words, vec![Some(String::from("one")), Some(String::from("two")), None, None, None]);
+    // the second wave waits for the pause after the last word of the first one
+    assert!(spawned[2].0 - spawned[1].0 >= 5.0);
+    assert!(run.is_finished());
+}
+
+#[test]
+fn stars_follow_the_score() {
+    let level = Level::parse("test", LEVEL).unwrap();
+
+    assert_eq!(level.stars_for(5.0), 0);
+    assert_eq!(level.stars_for(20.0), 2);
+    assert_eq!(level.stars_for(100.0), levels::MAX_STARS);
+    assert_eq!(levels::star_label(2), "**.");
+}
+
+#[test]
+fn levels_unlock_one_after_the_other() {
+    let levels = ["first", "second", "third"].iter().map(|id| Level::parse(id, LEVEL).unwrap()).collect::<Vec<Level>>();
+    let mut progress = CampaignProgress::default();
+
+    assert!(progress.is_unlocked(&levels, 0));
+    assert!(!progress.is_unlocked(&levels, 1));
+
+    assert!(progress.record("first", 2));
+    assert!(!progress.record("first", 1));
+    assert_eq!(progress.stars("first"), 2);
+    assert!(progress.is_unlocked(&levels, 1));
+    assert!(!progress.is_unlocked(&levels, 2));
+}
+
+#[test]
+fn campaign_levels_are_valid() {
+    let directory = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("resources/levels");
+    let mut count = 0;
+
+    for file in std::fs::read_dir(directory).unwrap() {
+        let path = file.unwrap().path();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let id = path.file_stem().unwrap().to_str().unwrap();
+
+        assert!(Level::parse(id, &text).is_ok(), "{}", path.display());
+        count += 1;
+    }
+
+    assert!(count > 0);
+}
+
+quickcheck! {
+    fn progress_survives_a_round_trip(stars: BTreeMap<String, usize>) -> bool {
+        let stars = stars.into_iter().
+            filter(|(level, _)| !level.is_empty() && !level.contains(['\t', '\n', '\r'])).
+            map(|(level, stars)| (level, stars % (levels::MAX_STARS + 1))).
+            collect();
+        let progress = CampaignProgress { stars };
+
+        CampaignProgress::parse(&progress.serialize()) == progress
+    }
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use quickcheck::quickcheck;
+
+use type_racer::entities::Word;
+use type_racer::game_state::GameState;
+use type_racer::modifiers::*;
+
+use common::mock_word;
+
+#[derive(Debug)]
+struct MockModifier {
+    schedule: Schedule,
+    stacking: Stacking
+}
+
+impl Modifier for MockModifier {
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    fn schedule(&self) -> Schedule {
+        self.schedule
+    }
+
+    fn duration(&self) -> f32 {
+        2.0
+    }
+
+    fn stacking(&self) -> Stacking {
+        self.stacking
+    }
+
+    fn on_start(&self, state: &mut GameState) {
+        state.game_speed_up += 1.0;
+    }
+
+    fn on_end(&self, state: &mut GameState) {
+        state.game_speed_up -= 1.0;
+    }
+
+    fn on_word_spawned(&self, word: &mut Word, _state: &mut GameState) {
+        word.is_reversed = true;
+    }
+}
+
+fn registry_with(schedule: Schedule, stacking: Stacking) -> ModifierRegistry {
+    let mut registry = ModifierRegistry::new();
+    registry.register(Box::new(MockModifier { schedule, stacking }));
+
+    registry
+}
+
+#[test]
+fn delayed_modifier_affects_words_after_its_delay() {
+    let mut registry = registry_with(Schedule::After(5.0), Stacking::Refresh);
+    let mut state = GameState::new();
+
+    let mut early_word = mock_word("early", 0.0, 100.0);
+    registry.on_word_spawned(&mut early_word, &mut state);
+    assert!(!early_word.is_reversed);
+
+    registry.update(5.0, &mut state);
+
+    let mut late_word = mock_word("late", 0.0, 100.0);
+    registry.on_word_spawned(&mut late_word, &mut state);
+    assert!(late_word.is_reversed);
+}
+
+#[test]
+fn random_modifier_starts_and_ends() {
+    let mut registry = registry_with(Schedule::Random { first: 1.0, min: 10.0, max: 20.0 }, Stacking::Refresh);
+    let mut state = GameState::new();
+
+    registry.update(0.5, &mut state);
+    assert!(!registry.is_active(0));
+
+    registry.update(0.5, &mut state);
+    assert!(registry.is_active(0));
+    assert_eq!(state.game_speed_up, 1.0);
+
+    registry.update(2.0, &mut state);
+    assert!(!registry.is_active(0));
+    assert_eq!(state.game_speed_up, 0.0);
+}
+
+#[test]
+fn refreshed_modifier_starts_once() {
+    let mut registry = registry_with(Schedule::Random { first: 100.0, min: 100.0, max: 200.0 }, Stacking::Refresh);
+    let mut state = GameState::new();
+
+    registry.activate(0, &mut state);
+    registry.update(1.5, &mut state);
+    registry.activate(0, &mut state);
+    assert_eq!(registry.running_count(0), 1);
+    assert_eq!(state.game_speed_up, 1.0);
+
+    // the second activation restarted the timer
+    registry.update(1.5, &mut state);
+    assert!(registry.is_active(0));
+}
+
+#[test]
+fn fog_bands_are_added_and_removed() {
+    let mut registry = ModifierRegistry::new();
+    registry.register(Box::new(Fog));
+    let mut state = GameState::new();
+
+    for _ in 0 .. Fog::MAX_BANDS + 1 {
+        registry.activate(0, &mut state);
+    }
+    assert_eq!(state.fog_bands.len(), Fog::MAX_BANDS);
+
+    registry.update(Fog::DURATION, &mut state);
+    assert!(state.fog_bands.is_empty());
+}
+
+quickcheck! {
+    fn stacked_modifier_never_exceeds_its_limit(max: u8, activations: u8) -> bool {
+        let max = (max % 5) as usize;
+        let mut registry = registry_with(Schedule::Random { first: 100.0, min: 100.0, max: 200.0 }, Stacking::Stack(max));
+        let mut state = GameState::new();
+
+        for _ in 0 .. activations {
+            registry.activate(0, &mut state);
+        }
+
+        let expected = max.min(activations as usize);
+        registry.running_count(0) == expected && state.game_speed_up == expected as f32
+    }
+}
@@ -0,0 +1,228 @@
+mod common;
+
+use ggez::event::KeyCode;
+use quickcheck::quickcheck;
+
+use type_racer::game_state::GameState;
+use type_racer::power_ups::*;
+
+use common::mock_word;
+
+#[derive(Debug)]
+struct MockTimedPowerUp;
+
+impl PowerUp for MockTimedPowerUp {
+    fn hud_label(&self) -> String {
+        String::from("mock")
+    }
+
+    fn description(&self) -> String {
+        String::from("for mock")
+    }
+
+    fn cost(&self) -> f32 {
+        10.0
+    }
+
+    fn cooldown(&self) -> f32 {
+        1.0
+    }
+
+    fn key_label(&self) -> &str {
+        "9"
+    }
+
+    fn hotkeys(&self) -> &[KeyCode] {
+        &[KeyCode::Key9]
+    }
+
+    fn duration(&self) -> Option<f32> {
+        Some(2.0)
+    }
+
+    fn apply(&self, state: &mut GameState) {
+        state.game_speed_up += 1.0;
+    }
+
+    fn expire(&self, state: &mut GameState) {
+        state.game_speed_up -= 1.0;
+    }
+}
+
+#[test]
+fn default_power_ups_are_found_by_hotkey() {
+    let registry = PowerUpRegistry::with_defaults();
+
+    assert_eq!(registry.find_by_key(KeyCode::Key1), Some(0));
+    assert_eq!(registry.find_by_key(KeyCode::Numpad2), Some(1));
+    assert_eq!(registry.find_by_key(KeyCode::Key3), Some(2));
+    assert_eq!(registry.find_by_key(KeyCode::A), None);
+}
+
+#[test]
+fn remove_words_needs_words_on_screen() {
+    let mut registry = PowerUpRegistry::with_defaults();
+    let mut state = GameState::new();
+    state.cash = RemoveWords::COST;
+
+    assert!(registry.buy(1, &mut state));
+    assert!(!registry.activate(1, &mut state));
+    assert_eq!(registry.owned(1), 1);
+
+    state.words.push(mock_word("one", 0.0, 100.0));
+    state.words.push(mock_word("two", 0.0, 100.0));
+    state.words.push(mock_word("three", 0.0, 100.0));
+
+    assert!(registry.activate(1, &mut state));
+    assert_eq!(registry.owned(1), 0);
+    assert_eq!(state.words.iter().filter(|word| word.is_typed).count(), RemoveWords::DEFAULT_COUNT);
+}
+
+#[test]
+fn timed_power_up_expires() {
+    let mut registry = PowerUpRegistry::new();
+    registry.register(Box::new(MockTimedPowerUp));
+    let mut state = GameState::new();
+    state.cash = 100.0;
+
+    assert!(registry.buy(0, &mut state));
+    assert!(registry.activate(0, &mut state));
+    assert_eq!(state.game_speed_up, 1.0);
+    assert_eq!(registry.remaining_time(0), Some(2.0));
+
+    registry.update(1.0, &mut state);
+    assert_eq!(state.game_speed_up, 1.0);
+
+    registry.update(1.5, &mut state);
+    assert_eq!(state.game_speed_up, 0.0);
+    assert_eq!(registry.remaining_time(0), None);
+}
+
+#[test]
+fn freeze_and_slow_motion_scale_word_time() {
+    let mut registry = PowerUpRegistry::with_defaults();
+    let mut state = GameState::new();
+    state.cash = TimeFreeze::COST + SlowMotion::COST;
+
+    assert!(registry.buy(5, &mut state) && registry.buy(3, &mut state));
+
+    assert!(registry.activate(5, &mut state));
+    assert_eq!(state.word_time_scale(), GameState::SLOW_MOTION_SCALE);
+
+    assert!(registry.activate(3, &mut state));
+    assert_eq!(state.word_time_scale(), 0.0);
+
+    registry.update(TimeFreeze::DURATION, &mut state);
+    assert_eq!(state.word_time_scale(), GameState::SLOW_MOTION_SCALE);
+
+    registry.update(SlowMotion::DURATION, &mut state);
+    assert_eq!(state.word_time_scale(), 1.0);
+}
+
+#[test]
+fn used_shield_stops_its_timer() {
+    let mut registry = PowerUpRegistry::with_defaults();
+    let mut state = GameState::new();
+    state.cash = Shield::COST;
+
+    assert!(registry.buy(4, &mut state));
+    assert!(registry.activate(4, &mut state));
+    assert!(state.shield_active);
+
+    state.shield_active = false;
+    registry.update(0.1, &mut state);
+    assert_eq!(registry.remaining_time(4), None);
+}
+
+#[test]
+fn bomb_clears_words_left_of_the_column() {
+    let mut registry = PowerUpRegistry::new();
+    registry.register(Box::new(Bomb { column: 100.0 }));
+    let mut state = GameState::new();
+    state.cash = Bomb::COST;
+    state.words.push(mock_word("near", 10.0, 100.0));
+    state.words.push(mock_word("far", 500.0, 100.0));
+
+    assert!(registry.buy(0, &mut state));
+    assert!(registry.activate(0, &mut state));
+    assert!(!state.words[0].is_typed);
+
+    registry.update(Bomb::FUSE, &mut state);
+    assert!(state.words[0].is_typed);
+    assert!(!state.words[1].is_typed);
+}
+
+#[test]
+fn used_buff_waits_for_its_cooldown() {
+    let mut registry = PowerUpRegistry::with_defaults();
+    let mut state = GameState::new();
+    state.cash = ExtraLife::COST * (1.0 + PowerUpRegistry::PRICE_GROWTH);
+
+    assert!(registry.buy(0, &mut state) && registry.buy(0, &mut state));
+    assert_eq!(registry.owned(0), 2);
+
+    assert!(registry.activate(0, &mut state));
+    assert_eq!(registry.cooldown_progress(0), 1.0);
+    assert!(!registry.activate(0, &mut state));
+
+    registry.update(ExtraLife::COOLDOWN, &mut state);
+    assert_eq!(registry.cooldown_progress(0), 0.0);
+    assert!(registry.activate(0, &mut state));
+    assert_eq!(state.remaining_lifes, GameState::INITIAL_LIFES + 2);
+}
+
+#[test]
+fn new_run_starts_with_a_fresh_registry() {
+    let mut registry = PowerUpRegistry::with_defaults();
+    let mut state = GameState::new();
+    state.cash = Shield::COST + Bomb::COST + ExtraLife::COST;
+    state.words.push(mock_word("near", 10.0, 100.0));
+
+    // a bought shield, a running bomb fuse and a raised price
+    assert!(registry.buy(4, &mut state) && registry.activate(4, &mut state));
+    assert!(registry.buy(6, &mut state) && registry.activate(6, &mut state));
+    assert!(registry.buy(0, &mut state));
+    assert!(!registry.active_timers().is_empty());
+
+    let registry = PowerUpRegistry::with_defaults();
+    assert!(registry.active_timers().is_empty());
+    assert!((0 .. registry.power_ups().len()).all(|index| {
+        registry.owned(index) == 0 && registry.cooldown_progress(index) == 0.0 && registry.price(index) == registry.power_ups()[index].cost()
+    }));
+}
+
+quickcheck! {
+    fn extra_life_is_bought_only_with_enough_cash(cash: f32) -> bool {
+        let mut registry = PowerUpRegistry::with_defaults();
+        let mut state = GameState::new();
+        state.cash = cash;
+
+        let bought = registry.buy(0, &mut state);
+
+        if cash >= ExtraLife::COST {
+            bought && registry.owned(0) == 1 && state.cash == cash - ExtraLife::COST
+        }
+        else {
+            !bought && registry.owned(0) == 0 && state.cash == cash
+        }
+    }
+
+    fn price_grows_with_every_purchase(purchases: u8) -> bool {
+        let mut registry = PowerUpRegistry::with_defaults();
+        let mut state = GameState::new();
+        let mut last_price = registry.price(1);
+
+        for _ in 0 .. purchases % 10 {
+            state.cash = registry.price(1);
+            registry.buy(1, &mut state);
+
+            if registry.price(1) <= last_price {
+                return false;
+            }
+
+            last_price = registry.price(1);
+        }
+
+        true
+    }
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use quickcheck::quickcheck;
+
+use type_racer::entities::WordKind;
+use type_racer::review::{ self, MistakeLog, ReviewSource, MASTERED_BOX };
+use type_racer::word_sources::WordSource;
+
+use common::mock_word;
+
+fn words(words: &[&str]) -> Vec<String> {
+    words.iter().map(|word| word.to_string()).collect()
+}
+
+#[test]
+fn missed_word_is_due_right_away() {
+    let mut log = MistakeLog::default();
+    log.record_miss("plenty", 100);
+    log.record_miss("quartz", 50);
+    log.record_miss("plenty", 120);
+
+    assert_eq!(log.due_words(120), words(&["quartz", "plenty"]));
+    assert_eq!(log.entries["plenty"].misses, 2);
+}
+
+#[test]
+fn typed_word_waits_longer_every_time_until_mastered() {
+    let mut log = MistakeLog::default();
+    log.record_miss("quartz", 0);
+    let mut now = 0;
+
+    for box_index in 1 ..= MASTERED_BOX {
+        log.record_success("quartz", now);
+        assert_eq!(log.entries["quartz"].box_index, box_index);
+
+        assert!(log.due_words(now).is_empty());
+        now = log.entries["quartz"].due;
+    }
+
+    assert!(log.entries["quartz"].is_mastered());
+    assert!(log.due_words(u64::MAX).is_empty());
+
+    log.record_miss("quartz", now);
+    assert_eq!(log.entries["quartz"].box_index, 0);
+}
+
+#[test]
+fn words_which_are_not_due_stay_in_their_box() {
+    let mut log = MistakeLog::default();
+    log.record_miss("plenty", 0);
+    log.record_success("plenty", 0);
+    log.record_success("plenty", 1);
+    log.record_success("unknown", 1);
+
+    assert_eq!(log.entries["plenty"].box_index, 1);
+    assert!(!log.entries.contains_key("unknown"));
+}
+
+#[test]
+fn only_words_of_the_pack_are_logged() {
+    let pack = words(&["elephant", "tiger"]);
+    let mut log = MistakeLog::default();
+    log.record_miss("plenty", 0);
+
+    let mut word = mock_word("elephant", 0.0, 100.0);
+    word.kind = WordKind::Splitting;
+    let (first, second) = word.split_labels().unwrap();
+
+    assert!(log.tracks(&word, &pack));
+    assert!(!log.tracks(&mock_word(&first, 0.0, 100.0), &pack));
+    assert!(!log.tracks(&mock_word(&second, 0.0, 100.0), &pack));
+    // a word of another pack which is already in the log
+    assert!(log.tracks(&mock_word("plenty", 0.0, 100.0), &pack));
+
+    let mut boss = mock_word("tiger", 0.0, 100.0);
+    boss.kind = WordKind::Boss { lives: 3 };
+    assert!(!log.tracks(&boss, &pack));
+}
+
+#[test]
+fn csv_has_a_header_and_quotes_commas() {
+    let mut log = MistakeLog::default();
+    log.record_miss("hello, world", 7);
+    log.record_miss("say \"hi\"", 7);
+
+    assert_eq!(log.to_csv(), format!("{}\n\"hello, world\",0,7,1,0,false\n\"say \"\"hi\"\"\",0,7,1,0,false\n", MistakeLog::CSV_HEADER));
+}
+
+#[test]
+fn review_source_stops_giving_typed_due_words() {
+    let mut source = ReviewSource::new(words(&["quartz"]), Vec::new(), Some(1));
+    assert_eq!(source.next_word(), Some(String::from("quartz")));
+
+    source.word_result("quartz", true);
+    assert_eq!(source.next_word(), None);
+
+    source.word_result("plenty", false);
+    assert_eq!(source.due(), ["plenty"]);
+    assert!(review::now() > 0);
+}
+
+quickcheck! {
+    fn log_survives_a_round_trip(misses: Vec<(String, u32)>) -> bool {
+        let mut log = MistakeLog::default();
+
+        for (word, now) in misses {
+            if !word.is_empty() && !word.contains(['\t', '\n', '\r']) {
+                log.record_miss(&word, now as u64);
+                log.record_success(&word, now as u64);
+            }
+        }
+
+        MistakeLog::parse(&log.serialize()) == log
+    }
+}
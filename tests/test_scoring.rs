@@ -0,0 +1,83 @@
+mod common;
+
+use quickcheck::quickcheck;
+
+use type_racer::game_state::GameState;
+use type_racer::scoring::*;
+
+use common::mock_word;
+
+#[test]
+fn combo_resets_after_a_miss() {
+    let mut combo = Combo::default();
+    combo.hit();
+    combo.hit();
+    assert!(combo.multiplier() > 1.0);
+
+    combo.reset();
+    assert_eq!(combo.multiplier(), 1.0);
+}
+
+#[test]
+fn rare_letters_are_worth_more() {
+    let scoring = ComboScoring;
+    let combo = Combo::default();
+    let plain = mock_word("moon", 0.0, 100.0);
+    let rare = mock_word("jazz", 0.0, 100.0);
+
+    assert_eq!(rare_letters_count("jazz"), 3);
+    assert!(scoring.score(&rare, &combo) > scoring.score(&plain, &combo));
+}
+
+#[test]
+fn power_up_kills_pay_no_score() {
+    let economy = Economy::default();
+    let word = mock_word("bomb", 0.0, 100.0);
+    let combo = Combo { streak: 10 };
+
+    let typed = economy.reward(&word, &combo, KillCause::Typed);
+    let removed = economy.reward(&word, &combo, KillCause::PowerUp);
+
+    assert!(typed.score > 0.0 && typed.cash > 0.0);
+    assert_eq!(removed.score, 0.0);
+    assert!(removed.cash < typed.cash);
+}
+
+quickcheck! {
+    fn combo_multiplier_grows_up_to_its_limit(hits: u8) -> bool {
+        let mut combo = Combo::default();
+        let mut last_multiplier = combo.multiplier();
+
+        for _ in 0 .. hits {
+            combo.hit();
+
+            if combo.multiplier() < last_multiplier || combo.multiplier() > Combo::MAX_MULTIPLIER {
+                return false;
+            }
+
+            last_multiplier = combo.multiplier();
+        }
+
+        true
+    }
+
+    fn longer_streak_never_scores_less(label: String, streak: u8) -> bool {
+        let scoring = ComboScoring;
+        let word = mock_word(&label, 0.0, 100.0);
+        let shorter = Combo { streak: streak as u32 };
+        let longer = Combo { streak: streak as u32 + 1 };
+
+        scoring.score(&word, &longer) >= scoring.score(&word, &shorter)
+    }
+
+    fn killed_word_pays_the_economy_reward(label: String, streak: u8) -> bool {
+        let mut state = GameState::new();
+        state.combo = Combo { streak: streak as u32 };
+        state.words.push(mock_word(&label, 0.0, 100.0));
+
+        let expected = state.economy.reward(&state.words[0], &state.combo, KillCause::PowerUp);
+        let reward = state.kill_word(0, KillCause::PowerUp);
+
+        reward == expected && state.score == expected.score && state.cash == expected.cash && state.words[0].is_typed
+    }
+}
@@ -0,0 +1,121 @@
+use quickcheck::quickcheck;
+
+use type_racer::training::{ self, TypingStats, AdaptiveSource };
+use type_racer::word_sources::WordSource;
+use type_racer::modes::GameMode;
+use type_racer::cli;
+
+// `presses` presses of the key, the first `errors` of them wrong
+fn typed(stats: &mut TypingStats, letter: char, presses: u32, errors: u32, latency: f32) {
+    for press in 0 .. presses {
+        stats.record(None, letter, press >= errors, Some(latency));
+    }
+}
+
+#[test]
+fn keys_and_pairs_count_errors_and_latency() {
+    let mut stats = TypingStats::default();
+    stats.record(None, 'T', true, None);
+    stats.record(Some('t'), 'h', false, Some(0.5));
+    stats.record(Some('t'), 'H', true, Some(0.25));
+
+    let h = stats.keys[&'h'];
+    assert_eq!((h.presses, h.errors), (2, 1));
+    assert_eq!(h.error_rate(), 0.5);
+    assert_eq!(h.mean_latency(), Some(0.375));
+    assert_eq!(stats.keys[&'t'].mean_latency(), None);
+    assert_eq!(stats.bigrams["th"].presses, 2);
+}
+
+#[test]
+fn rarely_typed_keys_are_not_weak() {
+    let mut stats = TypingStats::default();
+    typed(&mut stats, 'q', TypingStats::MIN_PRESSES - 1, TypingStats::MIN_PRESSES - 1, 0.2);
+    typed(&mut stats, 'z', 10, 5, 0.2);
+
+    assert_eq!(stats.key_weakness('q'), 0.0);
+    assert_eq!(stats.key_weakness('Z'), 0.5);
+    assert!(stats.word_weakness("zoo") > stats.word_weakness("qoo"));
+}
+
+#[test]
+fn slow_keys_are_weak() {
+    let mut stats = TypingStats::default();
+    typed(&mut stats, 'a', 10, 0, 0.1);
+    typed(&mut stats, 'b', 10, 0, 0.1);
+    typed(&mut stats, 'p', 10, 0, 0.4);
+
+    assert_eq!(stats.key_weakness('a'), 0.0);
+    assert!(stats.key_weakness('p') > 0.0);
+}
+
+#[test]
+fn improved_keys_come_most_improved_first() {
+    let mut history = TypingStats::default();
+    typed(&mut history, 'a', 10, 5, 0.3);
+    typed(&mut history, 'b', 10, 2, 0.3);
+    typed(&mut history, 'c', 10, 0, 0.2);
+
+    let mut session = TypingStats::default();
+    typed(&mut session, 'a', 10, 0, 0.3);
+    typed(&mut session, 'b', 10, 1, 0.3);
+    typed(&mut session, 'c', 10, 1, 0.1);
+    // never typed before, nothing to compare with
+    typed(&mut session, 'd', 10, 0, 0.1);
+
+    let keys = training::improvements(&history, &session).iter().map(|improvement| improvement.key).collect::<Vec<char>>();
+    assert_eq!(keys, ['a', 'b']);
+}
+
+#[test]
+fn adaptive_source_prefers_words_with_weak_keys() {
+    let mut stats = TypingStats::default();
+    typed(&mut stats, 'x', 10, 8, 0.2);
+    typed(&mut stats, 'a', 10, 0, 0.2);
+
+    let words = vec![String::from("aaa"), String::from("xax")];
+    let mut source = AdaptiveSource::new(words, &stats, Some(7));
+    let weak_words = (0 .. 1000).filter(|_| source.next_word().unwrap() == "xax").count();
+
+    assert!(weak_words > 700);
+    assert_eq!(AdaptiveSource::new(Vec::new(), &stats, None).next_word(), None);
+}
+
+#[test]
+fn mode_is_chosen_on_the_command_line() {
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>();
+
+    assert_eq!(cli::parse(args(&["--mode", "adaptive"])).unwrap().mode, GameMode::Adaptive);
+    assert_eq!(cli::parse(args(&[])).unwrap().mode, GameMode::Classic);
+    assert!(cli::parse(args(&["--mode", "zen"])).is_err());
+
+    for mode in GameMode::ALL {
+        assert_eq!(mode.next().previous(), mode);
+    }
+}
+
+quickcheck! {
+    fn stats_survive_a_round_trip(presses: Vec<(char, char, bool, u8)>) -> bool {
+        let mut stats = TypingStats::default();
+
+        for (previous, letter, correct, latency) in presses {
+            let (previous, letter) = (previous.to_ascii_lowercase(), letter.to_ascii_lowercase());
+
+            if previous.is_ascii_alphabetic() && letter.is_ascii_alphabetic() {
+                stats.record(Some(previous), letter, correct, Some(latency as f32 / 100.0));
+            }
+        }
+
+        TypingStats::parse(&stats.serialize()) == stats
+    }
+
+    fn merging_adds_the_presses(first: Vec<bool>, second: Vec<bool>) -> bool {
+        let (mut merged, mut other) = (TypingStats::default(), TypingStats::default());
+        first.iter().for_each(|correct| merged.record(None, 'k', *correct, None));
+        second.iter().for_each(|correct| other.record(None, 'k', *correct, None));
+        merged.merge(&other);
+
+        let presses = merged.keys.get(&'k').map(|stats| stats.presses).unwrap_or(0);
+        presses as usize == first.len() + second.len()
+    }
+}
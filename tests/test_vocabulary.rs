@@ -0,0 +1,76 @@
+mod common;
+
+use quickcheck::quickcheck;
+
+use type_racer::entities::WordKind;
+use type_racer::vocabulary::{ self, Pair, VocabularySource };
+use type_racer::word_sources::WordSource;
+use type_racer::dictionary;
+
+use common::mock_word;
+
+fn lines(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|line| line.to_string()).collect()
+}
+
+#[test]
+fn two_column_lines_are_pairs() {
+    let dictionary = lines(&["perro\tdog", " gato \t cat ", "house", "\tempty", "silla\t"]);
+
+    assert_eq!(vocabulary::answers(&dictionary), lines(&["dog", "cat", "house", "empty"]));
+    assert_eq!(vocabulary::pairs(&dictionary), vec![
+        Pair { prompt: String::from("perro"), answer: String::from("dog") },
+        Pair { prompt: String::from("gato"), answer: String::from("cat") }
+    ]);
+}
+
+#[test]
+fn source_gives_answers_with_their_prompts() {
+    let pairs = vocabulary::pairs(&lines(&["perro\tdog", "can\tdog", "gato\tcat"]));
+    let mut source = VocabularySource::new(pairs, Some(5));
+
+    for _ in 0 .. 20 {
+        let answer = source.next_word().unwrap();
+        assert!(answer == "dog" || answer == "cat");
+    }
+
+    assert_eq!(source.prompt("dog"), Some(String::from("perro")));
+    assert_eq!(source.prompt("horse"), None);
+}
+
+#[test]
+fn word_shows_the_prompt_and_expects_the_answer() {
+    let mut word = mock_word("dog", 0.0, 10.0);
+    word.kind = WordKind::Splitting;
+    word.set_prompt("perro");
+    word.is_reversed = true;
+    word.update_shown_label("do");
+
+    assert_eq!(word.label(), "dog");
+    assert_eq!(word.prompt(), Some("perro"));
+    assert_eq!(word.shown_label(), "perro");
+    assert_eq!(word.split_labels(), None);
+}
+
+#[test]
+fn dictionary_validation_checks_the_answers() {
+    let report = dictionary::validate("perro\tdog\ncan\tdog\n\tcat\nárbol\ttree\npájaro\tbírd\n");
+
+    assert_eq!(report.words, 2);
+    assert_eq!(report.pairs, 2);
+    assert_eq!(report.broken_pairs, vec![3]);
+    assert_eq!(report.duplicates.len(), 1);
+    assert_eq!(report.untypable.len(), 1);
+    assert_eq!(dictionary::normalize("perro\tdog\ncan\tdog\n\tcat\n"), "perro\tdog\n");
+}
+
+quickcheck! {
+    fn one_column_lines_are_their_own_answers(words: Vec<String>) -> bool {
+        let words = words.into_iter().
+            map(|word| word.replace('\t', "").trim().to_string()).
+            filter(|word| !word.is_empty()).
+            collect::<Vec<String>>();
+
+        vocabulary::answers(&words) == words && vocabulary::pairs(&words).is_empty()
+    }
+}
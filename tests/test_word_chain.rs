@@ -0,0 +1,62 @@
+use quickcheck::quickcheck;
+
+use type_racer::word_chain::{ Chain, ChainSource, FirstLetterIndex };
+use type_racer::word_sources::WordSource;
+
+fn words(words: &[&str]) -> Vec<String> {
+    words.iter().map(|word| word.to_string()).collect()
+}
+
+#[test]
+fn index_groups_words_by_first_letter() {
+    let index = FirstLetterIndex::new(&words(&["apple", "Ant", "egg", ""]));
+
+    assert_eq!(index.starting_with('A'), ["apple", "Ant"]);
+    assert_eq!(index.starting_with('e'), ["egg"]);
+    assert!(index.starting_with('z').is_empty());
+}
+
+#[test]
+fn chain_grows_and_breaks() {
+    let mut chain = Chain::default();
+
+    assert!(chain.typed("apple"));
+    assert!(chain.typed("Egg"));
+    assert!(chain.typed("goat"));
+    assert_eq!(chain.length(), 3);
+    assert_eq!(chain.next_letter(), Some('t'));
+    assert!(chain.multiplier() > 1.0);
+
+    assert!(!chain.typed("house"));
+    assert_eq!(chain.length(), 1);
+    assert_eq!(chain.multiplier(), 1.0);
+}
+
+#[test]
+fn source_gives_words_unlocked_by_the_last_typed_word() {
+    let mut source = ChainSource::new(words(&["apple", "egg", "eel", "tiger"]), Some(4));
+    source.word_result("apple", true);
+
+    for _ in 0 .. 20 {
+        assert!(source.next_word().unwrap().starts_with('e'));
+    }
+
+    // nothing starts with `r`, so any word goes
+    source.word_result("tiger", true);
+    assert!(source.next_word().is_some());
+
+    source.word_result("egg", false);
+    assert_eq!(source.chain().map(|chain| chain.length()), Some(1));
+    assert_eq!(ChainSource::new(Vec::new(), None).next_word(), None);
+}
+
+quickcheck! {
+    fn multiplier_never_exceeds_its_limit(words: Vec<String>) -> bool {
+        let mut chain = Chain::default();
+
+        words.iter().all(|word| {
+            chain.typed(word);
+            chain.multiplier() >= 1.0 && chain.multiplier() <= Chain::MAX_MULTIPLIER
+        })
+    }
+}
@@ -0,0 +1,100 @@
+use quickcheck::quickcheck;
+
+use type_racer::word_packs::{ self, Manifest, WordPack };
+use type_racer::filesystem_helper::{ insert_score, pack_scores };
+use type_racer::modes::GameMode;
+
+fn lines(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|line| line.to_string()).collect()
+}
+
+#[test]
+fn manifest_is_parsed() {
+    let manifest = Manifest::parse("# a comment
+name: Programming
+language: en
+author: Someone: Else
+description: Keywords
+tags: programming , technical,
+words: keywords.dict, jargon.dict
+homepage: ignored").unwrap();
+
+    assert_eq!(manifest.name, "Programming");
+    assert_eq!(manifest.language, "en");
+    assert_eq!(manifest.author, "Someone: Else");
+    assert_eq!(manifest.tags, vec!["programming", "technical"]);
+    assert_eq!(manifest.word_files, vec!["keywords.dict", "jargon.dict"]);
+}
+
+#[test]
+fn manifest_needs_a_name_and_valid_lines() {
+    assert_eq!(Manifest::parse("name: Minimal").unwrap().word_files, vec!["words.dict"]);
+    assert!(Manifest::parse("language: en").is_err());
+    assert!(Manifest::parse("name: Broken\nno colon here").is_err());
+    assert!(Manifest::parse("name: Empty\nwords: ,").is_err());
+}
+
+#[test]
+fn pack_files_live_in_the_pack_directory() {
+    let pack = WordPack { id: String::from("english"), manifest: Manifest::parse("name: English\ntags: common").unwrap() };
+
+    assert_eq!(WordPack::manifest_path("english"), "/packs/english/manifest.txt");
+    assert_eq!(pack.word_paths(), vec!["/packs/english/words.dict"]);
+    assert!(pack.summary().contains("Tags: common"));
+}
+
+#[test]
+fn shipped_packs_are_in_the_resources() {
+    let directory = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("resources/packs");
+
+    for pack in word_packs::SHIPPED_PACKS {
+        let manifest = std::fs::read_to_string(directory.join(pack).join(word_packs::MANIFEST_FILE)).unwrap();
+        assert!(Manifest::parse(&manifest).is_ok(), "{}", pack);
+    }
+}
+
+#[test]
+fn scores_are_tagged_with_their_pack() {
+    let saved = lines(&["old 50.00", "english\tbob 20.00", "programming\tann 90.00"]);
+    let updated = insert_score(&saved, "english", "eve", 30.0, 10);
+
+    assert_eq!(pack_scores(&updated, "english"), vec!["old 50.00", "eve 30.00", "bob 20.00"]);
+    assert_eq!(pack_scores(&updated, "programming"), vec!["ann 90.00"]);
+    assert!(updated.iter().all(|line| line.contains('\t')));
+}
+
+#[test]
+fn scoreboard_keeps_only_the_best_scores_of_the_pack() {
+    let saved = lines(&["english\tann 90.00", "english\tbob 20.00", "french\tzoe 1.00"]);
+    let updated = insert_score(&saved, "english", "eve", 5.0, 2);
+
+    assert_eq!(pack_scores(&updated, "english"), vec!["ann 90.00", "bob 20.00"]);
+    assert_eq!(pack_scores(&updated, "french"), vec!["zoe 1.00"]);
+}
+
+#[test]
+fn memory_mode_has_its_own_scoreboard() {
+    let lines = insert_score(&[], "memory/english", "ana", 12.0, 10);
+
+    assert!(GameMode::Memory.has_own_scoreboard());
+    assert!(!GameMode::Classic.has_own_scoreboard());
+    assert!(pack_scores(&lines, "english").is_empty());
+    assert_eq!(pack_scores(&lines, "memory/english"), ["ana 12.00"]);
+}
+
+quickcheck! {
+    fn pack_scoreboard_stays_sorted(scores: Vec<u16>) -> bool {
+        let mut saved = Vec::new();
+
+        for score in scores {
+            saved = insert_score(&saved, "english", "player", score as f32, 10);
+        }
+
+        let scoreboard = pack_scores(&saved, "english").
+            iter().
+            map(|entry| entry.rsplit(' ').next().unwrap().parse::<f32>().unwrap()).
+            collect::<Vec<f32>>();
+
+        scoreboard.len() <= 10 && scoreboard.windows(2).all(|pair| pair[0] >= pair[1])
+    }
+}
@@ -0,0 +1,107 @@
+use quickcheck::quickcheck;
+
+use type_racer::word_sources::{ WordSource, DictionarySource, MarkovGenerator, PassageSource };
+use type_racer::cli::{ self, SourceKind };
+use type_racer::difficulty::word_difficulty;
+
+fn training_words() -> Vec<String> {
+    ["banana", "bandana", "cabana", "canal", "panama", "cantata", "nana", "alpaca", "papaya", "anaconda"].
+        iter().
+        map(|word| word.to_string()).
+        collect()
+}
+
+#[test]
+fn dictionary_source_picks_words_from_the_list() {
+    let words = training_words();
+    let mut source = DictionarySource::new(words.clone(), None);
+
+    for _ in 0 .. 20 {
+        assert!(words.contains(&source.next_word().unwrap()));
+    }
+
+    assert_eq!(DictionarySource::new(Vec::new(), None).next_word(), None);
+}
+
+#[test]
+fn dictionary_source_follows_the_difficulty_level() {
+    let words = (1 ..= 100).map(|length| "a".repeat(length)).collect::<Vec<String>>();
+    let mut source = DictionarySource::new(words, Some(3));
+
+    for _ in 0 .. 20 {
+        assert!(source.next_word().unwrap().len() <= 21);
+    }
+
+    source.set_difficulty(1.0);
+
+    for _ in 0 .. 20 {
+        assert!(source.next_word().unwrap().len() >= 80);
+    }
+}
+
+#[test]
+fn passage_source_keeps_the_text_order_and_starts_over() {
+    let mut source = PassageSource::new("Oh dear! a daisy-chain, (late)");
+    let words = (0 .. 5).map(|_| source.next_word().unwrap()).collect::<Vec<String>>();
+
+    assert_eq!(words, vec!["Oh", "dear", "a", "daisy-chain", "late"]);
+    assert_eq!(source.next_word().unwrap(), "Oh");
+    assert_eq!(PassageSource::new(" ! ").next_word(), None);
+}
+
+#[test]
+fn same_seed_generates_the_same_words() {
+    let words = training_words();
+    let mut first = MarkovGenerator::new(&words, 2, 3, 8, Some(42));
+    let mut second = MarkovGenerator::new(&words, 2, 3, 8, Some(42));
+
+    for _ in 0 .. 10 {
+        assert_eq!(first.next_word(), second.next_word());
+    }
+}
+
+#[test]
+fn generator_trained_on_nothing_gives_nothing() {
+    let mut generator = MarkovGenerator::new(&[], 2, 3, 8, Some(1));
+
+    assert_eq!(generator.next_word(), None);
+}
+
+#[test]
+fn source_options_are_parsed() {
+    let args = ["--source", "generated", "--order", "2", "--seed", "7"].iter().map(|arg| arg.to_string());
+    let options = cli::parse(args).unwrap();
+
+    assert_eq!(options.source, SourceKind::Generated);
+    assert_eq!(options.order, 2);
+    assert_eq!(options.seed, Some(7));
+    assert_eq!(cli::parse(Vec::new()).unwrap().source, SourceKind::Dictionary);
+    assert!(cli::parse(vec![String::from("--source")]).is_err());
+    assert!(cli::parse(vec![String::from("--order"), String::from("two")]).is_err());
+    assert!(cli::parse(vec![String::from("--min-length"), String::from("10")]).is_err());
+}
+
+quickcheck! {
+    fn generated_words_are_new_and_fit_the_length(order: u8, min_length: u8, extra_length: u8, seed: u64) -> bool {
+        let order = (order % 3 + 1) as usize;
+        let min_length = (min_length % 5 + 2) as usize;
+        let max_length = min_length + (extra_length % 5) as usize;
+        let mut generator = MarkovGenerator::new(&training_words(), order, min_length, max_length, Some(seed));
+
+        let words = (0 .. 5).filter_map(|_| generator.next_word()).collect::<Vec<String>>();
+
+        words.iter().all(|word| {
+            let length = word.chars().count();
+            length >= min_length && length <= max_length && !generator.is_known(word)
+        })
+    }
+
+    fn dictionary_is_sorted_by_difficulty(words: Vec<String>, level: f32) -> bool {
+        let mut source = DictionarySource::new(words, None);
+        source.set_difficulty(level);
+        let (first, last) = source.selection_range();
+        let sorted = source.words().windows(2).all(|pair| word_difficulty(&pair[0]) <= word_difficulty(&pair[1]));
+
+        sorted && (source.words().is_empty() || (first <= last && last < source.words().len()))
+    }
+}